@@ -1,5 +1,7 @@
+use serde::{Deserialize, Serialize};
 use xcb::x::KeyButMask;
 
+use crate::config_loader::UnresolvedConfig;
 use crate::keysyms::Keysym;
 
 #[derive(Debug)]
@@ -14,10 +16,26 @@ pub struct Config {
     pub(crate) actions: Vec<Action>,
     /// List of all `commands` defined in the configuration file
     pub(crate) commands: Vec<Command>,
+    /// List of all `mouse_bindings` defined in the configuration file, when none are defined
+    /// `Leader` + `Button1` moves a client and `Leader` + `Button3` resizes it
+    pub(crate) mouse_bindings: Vec<MouseBinding>,
     /// total workspaces to display, this is constrained to >= 1 and <= 10.
     pub(crate) workspaces: u8,
     /// the size of the border to be used by the frames
     pub(crate) border_width: u16,
+    /// fraction of the screen width used by the master column on tall layouts, this is
+    /// constrained to >= 0.1 and <= 0.9.
+    /// default: 0.5
+    pub(crate) master_ratio: f32,
+    /// space in pixels between adjacent tiled clients
+    /// default: 0
+    pub(crate) inner_gap: u16,
+    /// space in pixels between tiled clients and the edges of the screen
+    /// default: 0
+    pub(crate) outer_gap: u16,
+    /// disables gaps on workspaces with a single client
+    /// default: true
+    pub(crate) smart_gaps: bool,
     /// color to be used by borders
     pub(crate) border_color: u32,
     /// color to be used by the active client border
@@ -28,8 +46,21 @@ pub struct Config {
     /// wether or not the focus should follow the cursor, focusing hovered clients
     /// default: true
     pub(crate) focus_follow_mouse: bool,
+    /// whether the configuration file is reloaded automatically when it changes
+    /// default: false
+    pub(crate) auto_reload: bool,
+    /// whether every screen has its own workspaces, or all screens share the same ones
+    /// default: screen
+    pub(crate) workspace_mode: WorkspaceMode,
+    /// what happens when switching to a workspace that is displayed on another screen, only
+    /// used when workspaces are shared between screens
+    /// default: swap
+    pub(crate) workspace_switch: WorkspaceSwitch,
     /// commands to be executed during window manager startup
     pub(crate) startup_commands: Vec<AutoCommand>,
+    /// List of all `rules` defined in the configuration file, the first rule matching a new
+    /// window is applied to it
+    pub(crate) rules: Vec<WindowRule>,
 }
 
 impl Config {
@@ -41,6 +72,10 @@ impl Config {
         &self.commands
     }
 
+    pub fn mouse_bindings(&self) -> &[MouseBinding] {
+        &self.mouse_bindings
+    }
+
     pub fn workspaces(&self) -> u8 {
         self.workspaces
     }
@@ -49,6 +84,22 @@ impl Config {
         self.border_width
     }
 
+    pub fn master_ratio(&self) -> f32 {
+        self.master_ratio
+    }
+
+    pub fn inner_gap(&self) -> u16 {
+        self.inner_gap
+    }
+
+    pub fn outer_gap(&self) -> u16 {
+        self.outer_gap
+    }
+
+    pub fn smart_gaps(&self) -> bool {
+        self.smart_gaps
+    }
+
     pub fn border_color(&self) -> u32 {
         self.border_color
     }
@@ -65,20 +116,60 @@ impl Config {
         self.focus_follow_mouse
     }
 
+    pub fn auto_reload(&self) -> bool {
+        self.auto_reload
+    }
+
+    pub fn workspace_mode(&self) -> WorkspaceMode {
+        self.workspace_mode
+    }
+
+    pub fn workspace_switch(&self) -> WorkspaceSwitch {
+        self.workspace_switch
+    }
+
     pub fn startup_commands(&self) -> &[AutoCommand] {
         &self.startup_commands
     }
 
+    pub fn rules(&self) -> &[WindowRule] {
+        &self.rules
+    }
+
     pub fn update(&mut self, other: Config) {
         self.leader = other.leader;
         self.actions = other.actions;
         self.commands = other.commands;
+        self.mouse_bindings = other.mouse_bindings;
         self.workspaces = other.workspaces;
         self.border_width = other.border_width;
+        self.master_ratio = other.master_ratio;
+        self.inner_gap = other.inner_gap;
+        self.outer_gap = other.outer_gap;
+        self.smart_gaps = other.smart_gaps;
         self.border_color = other.border_color;
         self.active_border_color = other.active_border_color;
         self.focus_new_clients = other.focus_new_clients;
         self.focus_follow_mouse = other.focus_follow_mouse;
+        self.auto_reload = other.auto_reload;
+        self.workspace_mode = other.workspace_mode;
+        self.workspace_switch = other.workspace_switch;
+        self.rules = other.rules;
+        self.startup_commands = other.startup_commands;
+    }
+}
+
+/// minimal keymap used when the configuration file can't be loaded
+static FALLBACK_CONFIG: &str = include_str!("fallback.toml");
+
+impl Config {
+    /// Configuration with just enough bindings to reload the configuration file once it is
+    /// fixed, or to quit
+    pub fn fallback() -> Self {
+        toml::from_str::<UnresolvedConfig>(FALLBACK_CONFIG)
+            .ok()
+            .and_then(|config| Config::try_from(config).ok())
+            .expect("fallback configuration should always be valid")
     }
 }
 
@@ -89,16 +180,47 @@ impl Default for Config {
             border_color: 0x252525,
             focus_new_clients: true,
             focus_follow_mouse: true,
+            auto_reload: false,
+            workspace_mode: WorkspaceMode::default(),
+            workspace_switch: WorkspaceSwitch::default(),
             border_width: 4,
+            master_ratio: 0.5,
+            inner_gap: 0,
+            outer_gap: 0,
+            smart_gaps: true,
             workspaces: 9,
             leader: AvailableLeaderKeys::Mod1,
             actions: vec![],
             commands: vec![],
+            // 0x0008 maps to `Mod1`, which is the default leader key
+            mouse_bindings: MouseBinding::defaults(0x00000008),
             startup_commands: vec![],
+            rules: vec![],
         }
     }
 }
 
+/// How workspaces are distributed between screens
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub enum WorkspaceMode {
+    /// every screen has its own set of workspaces
+    #[default]
+    Screen,
+    /// every screen displays one workspace of a single set shared between all of them
+    Global,
+}
+
+/// What to do when switching to a workspace that is displayed on another screen
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub enum WorkspaceSwitch {
+    /// the workspace is brought to the active screen, and the other screen displays the
+    /// workspace that was active
+    #[default]
+    Swap,
+    /// the screen displaying the workspace becomes the active screen
+    Focus,
+}
+
 #[derive(Default, Debug, Clone)]
 pub enum AvailableLeaderKeys {
     #[default]
@@ -108,7 +230,7 @@ pub enum AvailableLeaderKeys {
 }
 
 /// All the actions available for any given key combination
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AvailableActions {
     /// focus the client immediatly to the left
     FocusLeft,
@@ -132,6 +254,8 @@ pub enum AvailableActions {
     Quit,
     /// Reloads the configuration file
     Reload,
+    /// Restarts lucky in place, keeping every client where it was
+    Restart,
     /// switches to workspace 1
     Workspace1,
     /// switches to workspace 2
@@ -152,6 +276,24 @@ pub enum AvailableActions {
     Workspace9,
     /// set focused client to be fullscreen
     Fullscreen,
+    /// grows the master column of the active workspace
+    GrowMaster,
+    /// shrinks the master column of the active workspace
+    ShrinkMaster,
+    /// increases the gaps of the active workspace
+    IncreaseGaps,
+    /// decreases the gaps of the active workspace
+    DecreaseGaps,
+    /// enables or disables the gaps of the active workspace
+    ToggleGaps,
+    /// adds one more client to the master column of the active workspace
+    IncMaster,
+    /// removes one client from the master column of the active workspace
+    DecMaster,
+    /// toggles whether the focused client floats above the layout
+    ToggleFloating,
+    /// switches the active workspace to the next available layout
+    NextLayout,
     /// move the focused client to workspace 1
     MoveToWorkspace1,
     /// move the focused client to workspace 2
@@ -170,6 +312,27 @@ pub enum AvailableActions {
     MoveToWorkspace8,
     /// move the focused client to workspace 9
     MoveToWorkspace9,
+    /// focus screen 1
+    FocusScreen1,
+    /// focus screen 2
+    FocusScreen2,
+    /// focus screen 3
+    FocusScreen3,
+    /// focus screen 4
+    FocusScreen4,
+    /// focus the screen after the active one, wrapping around to the first screen
+    FocusNextScreen,
+    /// move the focused client to screen 1
+    MoveToScreen1,
+    /// move the focused client to screen 2
+    MoveToScreen2,
+    /// move the focused client to screen 3
+    MoveToScreen3,
+    /// move the focused client to screen 4
+    MoveToScreen4,
+    /// move the focused client to the screen after the active one, wrapping around to the
+    /// first screen
+    MoveToNextScreen,
 }
 
 #[derive(Debug)]
@@ -194,6 +357,103 @@ pub struct Command {
     pub(crate) args: Vec<String>,
 }
 
+/// All the actions available for a mouse drag
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseAction {
+    /// moves the client under the cursor along with it, making it float
+    Move,
+    /// resizes the client under the cursor from its bottom right corner, making it float
+    Resize,
+}
+
+/// A rule applied to new windows matching every property it defines, properties that are not
+/// defined match any window
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct WindowRule {
+    /// second string of `WM_CLASS`, eg: `Firefox`
+    pub(crate) class: Option<String>,
+    /// first string of `WM_CLASS`, eg: `Navigator`
+    pub(crate) instance: Option<String>,
+    /// matches any window whose title contains this
+    pub(crate) title: Option<String>,
+    /// one of `_NET_WM_WINDOW_TYPE`, without the prefix, eg: `dialog` or `splash`
+    pub(crate) window_type: Option<String>,
+    /// index of the workspace the window is placed on
+    pub(crate) workspace: Option<u8>,
+    /// index of the screen the window is placed on
+    pub(crate) screen: Option<usize>,
+    pub(crate) floating: bool,
+    pub(crate) fullscreen: bool,
+    /// position and size of the window, as `[x, y, width, height]`, which makes it float
+    pub(crate) geometry: Option<(i32, i32, u32, u32)>,
+    pub(crate) no_border: bool,
+    /// whether the window should be focused when mapped
+    /// default: true
+    pub(crate) focus: bool,
+}
+
+impl WindowRule {
+    /// whether the rule applies to a window with the given properties, `window_types` are
+    /// the `_NET_WM_WINDOW_TYPE` names without the prefix
+    pub fn matches(
+        &self,
+        class: &str,
+        instance: &str,
+        title: &str,
+        window_types: &[String],
+    ) -> bool {
+        self.class.as_ref().is_none_or(|c| c.eq(class))
+            && self.instance.as_ref().is_none_or(|i| i.eq(instance))
+            && self
+                .title
+                .as_ref()
+                .is_none_or(|t| title.contains(t.as_str()))
+            && self.window_type.as_ref().is_none_or(|window_type| {
+                window_types
+                    .iter()
+                    .any(|other| other.eq_ignore_ascii_case(window_type))
+            })
+    }
+
+    pub fn workspace(&self) -> Option<u8> {
+        self.workspace
+    }
+
+    pub fn screen(&self) -> Option<usize> {
+        self.screen
+    }
+
+    pub fn floating(&self) -> bool {
+        self.floating || self.geometry.is_some()
+    }
+
+    pub fn fullscreen(&self) -> bool {
+        self.fullscreen
+    }
+
+    pub fn geometry(&self) -> Option<(i32, i32, u32, u32)> {
+        self.geometry
+    }
+
+    pub fn no_border(&self) -> bool {
+        self.no_border
+    }
+
+    pub fn focus(&self) -> bool {
+        self.focus
+    }
+}
+
+#[derive(Debug)]
+pub struct MouseBinding {
+    /// Bitflag modifiers required to execute this binding, example: `0x0008` maps to `Mod1`
+    pub(crate) modifier: u32,
+    /// The pointer button used for this binding, from 1 to 5
+    pub(crate) button: u8,
+    /// What happens to the client while the cursor is dragged
+    pub(crate) action: MouseAction,
+}
+
 #[derive(Debug)]
 pub struct AutoCommand {
     /// The string to be spawned when this command is called
@@ -247,6 +507,36 @@ impl Command {
     }
 }
 
+impl MouseBinding {
+    /// bindings used when the configuration file doesn't define any
+    pub(crate) fn defaults(modifier: u32) -> Vec<MouseBinding> {
+        vec![
+            MouseBinding {
+                modifier,
+                button: 1,
+                action: MouseAction::Move,
+            },
+            MouseBinding {
+                modifier,
+                button: 3,
+                action: MouseAction::Resize,
+            },
+        ]
+    }
+
+    pub fn modifiers(&self) -> u32 {
+        self.modifier
+    }
+
+    pub fn button(&self) -> u8 {
+        self.button
+    }
+
+    pub fn action(&self) -> MouseAction {
+        self.action
+    }
+}
+
 impl AutoCommand {
     pub fn command(&self) -> &str {
         &self.command
@@ -0,0 +1,135 @@
+use crate::config::Config;
+use crate::keysyms::Keysym;
+use std::collections::HashMap;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+/// Validates a configuration file without applying it, returning every problem found.
+///
+/// Besides failing to load, a configuration is invalid when two bindings use the same key
+/// combination, or when a command can't be found on `PATH`
+pub fn check_config<P>(path: P) -> Vec<String>
+where
+    P: AsRef<Path>,
+{
+    let config = match crate::load_config_from_file(path) {
+        Ok(config) => config,
+        Err(e) => return vec![format!("{e:#}")],
+    };
+
+    let mut problems = conflicting_bindings(&config);
+
+    let commands = config
+        .commands()
+        .iter()
+        .map(|command| command.command())
+        .chain(
+            config
+                .startup_commands()
+                .iter()
+                .map(|command| command.command()),
+        );
+    for command in commands {
+        if !is_executable_on_path(command) {
+            problems.push(format!("command `{command}` was not found on PATH"));
+        }
+    }
+
+    problems
+}
+
+/// every key combination bound more than once, either to actions or commands
+fn conflicting_bindings(config: &Config) -> Vec<String> {
+    let mut bindings: HashMap<(u32, Keysym), Vec<String>> = HashMap::new();
+
+    for action in config.actions() {
+        bindings
+            .entry((action.modifiers().inner(), action.key()))
+            .or_default()
+            .push(format!("action {:?}", action.action()));
+    }
+
+    for command in config.commands() {
+        bindings
+            .entry((command.modifiers(), command.key()))
+            .or_default()
+            .push(format!("command `{}`", command.command()));
+    }
+
+    let mut conflicts = bindings
+        .into_iter()
+        .filter(|(_, targets)| targets.len().gt(&1))
+        .map(|((modifiers, key), targets)| {
+            format!(
+                "{}{key} is bound more than once: {}",
+                modifier_names(modifiers),
+                targets.join(", ")
+            )
+        })
+        .collect::<Vec<_>>();
+    conflicts.sort();
+    conflicts
+}
+
+/// human readable modifiers of a binding, eg: `Mod1+Shift+`
+fn modifier_names(modifiers: u32) -> String {
+    [
+        (0x00000008, "Mod1"),
+        (0x00000004, "Control"),
+        (0x00000001, "Shift"),
+    ]
+    .into_iter()
+    .filter(|(mask, _)| modifiers & mask != 0)
+    .map(|(_, name)| format!("{name}+"))
+    .collect()
+}
+
+/// whether `command` is an executable file, looked up on `PATH` unless it is already a path
+fn is_executable_on_path(command: &str) -> bool {
+    let is_executable = |path: PathBuf| {
+        path.metadata()
+            .is_ok_and(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+    };
+
+    if command.contains('/') {
+        return is_executable(PathBuf::from(command));
+    }
+
+    std::env::var_os("PATH").is_some_and(|paths| {
+        std::env::split_paths(&paths).any(|path| is_executable(path.join(command)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duplicated_key_combinations_are_reported() {
+        let config = r##"
+            leader = "Mod1"
+            workspaces = 9
+            border_color = "#252525"
+            active_border_color = "#2D4F67"
+
+            [[actions]]
+            modifiers = ["Leader", "Shift"]
+            key = "r"
+            action = "Reload"
+
+            [[commands]]
+            modifiers = ["Leader", "Shift"]
+            key = "r"
+            command = "sh"
+        "##;
+        let config = toml::from_str::<crate::config_loader::UnresolvedConfig>(config)
+            .ok()
+            .and_then(|config| Config::try_from(config).ok())
+            .expect("config should be valid");
+
+        assert_eq!(
+            conflicting_bindings(&config),
+            vec!["Mod1+Shift+r is bound more than once: action Reload, command `sh`"]
+        );
+    }
+}
@@ -3,7 +3,8 @@ use std::ops::Add;
 use crate::{
     color_parser::Color,
     config::{
-        Action, ActionModifier, AutoCommand, AvailableActions, AvailableLeaderKeys, Command, Config,
+        Action, ActionModifier, AutoCommand, AvailableActions, AvailableLeaderKeys, Command,
+        Config, MouseAction, MouseBinding, WindowRule, WorkspaceMode, WorkspaceSwitch,
     },
 };
 use serde::Deserialize;
@@ -12,14 +13,38 @@ use serde::Deserialize;
 pub struct UnresolvedConfig {
     workspaces: u8,
     border_width: Option<u16>,
+    master_ratio: Option<f32>,
+    inner_gap: Option<u16>,
+    outer_gap: Option<u16>,
+    smart_gaps: Option<bool>,
     border_color: Option<String>,
     focus_follow_mouse: Option<bool>,
     active_border_color: Option<String>,
     focus_new_clients: Option<bool>,
+    auto_reload: Option<bool>,
+    workspace_mode: Option<UnresolvedWorkspaceMode>,
+    workspace_switch: Option<UnresolvedWorkspaceSwitch>,
     leader: UnresolvedLeader,
     actions: Vec<UnresolvedActionEntry>,
     commands: Vec<UnresolvedCommandEntry>,
+    mouse_bindings: Option<Vec<UnresolvedMouseBindingEntry>>,
     startup_commands: Option<Vec<String>>,
+    rules: Option<Vec<UnresolvedRuleEntry>>,
+}
+
+#[derive(Deserialize)]
+struct UnresolvedRuleEntry {
+    class: Option<String>,
+    instance: Option<String>,
+    title: Option<String>,
+    window_type: Option<String>,
+    workspace: Option<u8>,
+    screen: Option<usize>,
+    floating: Option<bool>,
+    fullscreen: Option<bool>,
+    geometry: Option<(i32, i32, u32, u32)>,
+    no_border: Option<bool>,
+    focus: Option<bool>,
 }
 
 #[derive(Deserialize)]
@@ -29,6 +54,20 @@ enum UnresolvedLeader {
     Mod1,
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum UnresolvedWorkspaceMode {
+    Screen,
+    Global,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum UnresolvedWorkspaceSwitch {
+    Swap,
+    Focus,
+}
+
 #[derive(Deserialize, Clone)]
 enum UnresolvedModifier {
     Leader,
@@ -51,6 +90,19 @@ struct UnresolvedCommandEntry {
     command: String,
 }
 
+#[derive(Deserialize)]
+struct UnresolvedMouseBindingEntry {
+    modifiers: Vec<UnresolvedModifier>,
+    button: u8,
+    action: UnresolvedMouseAction,
+}
+
+#[derive(Deserialize)]
+enum UnresolvedMouseAction {
+    Move,
+    Resize,
+}
+
 #[derive(Deserialize)]
 enum UnresolvedAction {
     FocusLeft,
@@ -63,6 +115,7 @@ enum UnresolvedAction {
     MoveRight,
     Close,
     Reload,
+    Restart,
     Quit,
     Workspace1,
     Workspace2,
@@ -82,16 +135,69 @@ enum UnresolvedAction {
     MoveToWorkspace7,
     MoveToWorkspace8,
     MoveToWorkspace9,
+    FocusScreen1,
+    FocusScreen2,
+    FocusScreen3,
+    FocusScreen4,
+    FocusNextScreen,
+    MoveToScreen1,
+    MoveToScreen2,
+    MoveToScreen3,
+    MoveToScreen4,
+    MoveToNextScreen,
     Fullscreen,
+    GrowMaster,
+    ShrinkMaster,
+    IncMaster,
+    DecMaster,
+    IncreaseGaps,
+    DecreaseGaps,
+    ToggleGaps,
+    ToggleFloating,
+    NextLayout,
 }
 
 pub enum ConfigError {
+    /// the file is not valid toml, or doesn't match the expected structure
+    Parse {
+        message: String,
+        line: usize,
+        column: usize,
+    },
     Key(String),
     Workspaces(String),
     BorderWidth(String),
     BorderColor(String),
     InvalidCommand(String),
     Color(String),
+    MouseButton(String),
+    MasterRatio(String),
+    Rule(String),
+}
+
+impl ConfigError {
+    /// parse error pointing to where the toml error happened on `source`
+    pub fn parse(error: toml::de::Error, source: &str) -> Self {
+        let (line, column) = error
+            .span()
+            .map(|span| line_and_column(source, span.start))
+            .unwrap_or((1, 1));
+
+        ConfigError::Parse {
+            message: error.message().to_string(),
+            line,
+            column,
+        }
+    }
+}
+
+/// 1-based line and column of a byte `offset` of `source`
+fn line_and_column(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset.min(source.len())];
+    let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+    let line = before.matches('\n').count() + 1;
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
 }
 
 impl From<AvailableLeaderKeys> for UnresolvedModifier {
@@ -131,6 +237,25 @@ impl TryFrom<UnresolvedConfig> for Config {
             })
         });
 
+        let mouse_bindings = match value.mouse_bindings {
+            Some(mut mouse_bindings) => {
+                mouse_bindings.iter_mut().for_each(|binding| {
+                    binding.modifiers.iter_mut().for_each(|modifier| {
+                        if let UnresolvedModifier::Leader = modifier {
+                            *modifier = leader.clone().into();
+                        }
+                    })
+                });
+
+                let mut resolved: Vec<MouseBinding> = vec![];
+                for binding in mouse_bindings.into_iter() {
+                    resolved.push(binding.try_into()?);
+                }
+                resolved
+            }
+            None => MouseBinding::defaults(u32::from(UnresolvedModifier::from(leader.clone()))),
+        };
+
         let mut actions: Vec<Action> = vec![];
         for action in value.actions.into_iter() {
             actions.push(action.try_into()?);
@@ -153,6 +278,18 @@ impl TryFrom<UnresolvedConfig> for Config {
             )));
         }
 
+        let mut rules: Vec<WindowRule> = vec![];
+        for rule in value.rules.unwrap_or_default().into_iter() {
+            rules.push(resolve_rule(rule, value.workspaces)?);
+        }
+
+        let master_ratio = value.master_ratio.unwrap_or(0.5);
+        if !(0.1..=0.9).contains(&master_ratio) {
+            return Err(ConfigError::MasterRatio(format!(
+                "master_ratio = {master_ratio}: master ratio must be between 0.1 and 0.9"
+            )));
+        }
+
         let border_color = Color::try_from(value.border_color.unwrap_or_default())
             .map_err(|e| ConfigError::BorderColor(e.to_string()))?
             .0;
@@ -164,18 +301,83 @@ impl TryFrom<UnresolvedConfig> for Config {
         Ok(Config {
             workspaces: value.workspaces,
             border_width: value.border_width.unwrap_or(1),
+            master_ratio,
+            inner_gap: value.inner_gap.unwrap_or(0),
+            outer_gap: value.outer_gap.unwrap_or(0),
+            smart_gaps: value.smart_gaps.unwrap_or(true),
             border_color,
             active_border_color,
             focus_follow_mouse: value.focus_follow_mouse.unwrap_or(true),
             focus_new_clients: value.focus_new_clients.unwrap_or(true),
+            auto_reload: value.auto_reload.unwrap_or(false),
+            workspace_mode: match value.workspace_mode {
+                Some(UnresolvedWorkspaceMode::Global) => WorkspaceMode::Global,
+                Some(UnresolvedWorkspaceMode::Screen) | None => WorkspaceMode::Screen,
+            },
+            workspace_switch: match value.workspace_switch {
+                Some(UnresolvedWorkspaceSwitch::Focus) => WorkspaceSwitch::Focus,
+                Some(UnresolvedWorkspaceSwitch::Swap) | None => WorkspaceSwitch::Swap,
+            },
             actions,
             leader,
             commands,
+            mouse_bindings,
             startup_commands,
+            rules,
         })
     }
 }
 
+/// rules refer to workspaces and screens starting from 1, like the actions do, but they are
+/// stored starting from 0, like everywhere else
+fn resolve_rule(value: UnresolvedRuleEntry, workspaces: u8) -> Result<WindowRule, ConfigError> {
+    if value.class.is_none()
+        && value.instance.is_none()
+        && value.title.is_none()
+        && value.window_type.is_none()
+    {
+        return Err(ConfigError::Rule(
+            "rules must match on at least one of class, instance, title or window_type".to_string(),
+        ));
+    }
+
+    if let Some(workspace) = value.workspace {
+        if workspace.eq(&0) || workspace.gt(&workspaces) {
+            return Err(ConfigError::Rule(format!(
+                "workspace = {workspace}: rule workspace must be between 1 and {workspaces}"
+            )));
+        }
+    }
+
+    if value.screen.is_some_and(|screen| screen.eq(&0)) {
+        return Err(ConfigError::Rule(
+            "screen = 0: rule screen must be greater than 0".to_string(),
+        ));
+    }
+
+    if let Some((_, _, width, height)) = value.geometry {
+        if width.eq(&0) || height.eq(&0) {
+            return Err(ConfigError::Rule(format!(
+                "geometry width and height must be greater than 0, got {width}x{height}"
+            )));
+        }
+    }
+
+    Ok(WindowRule {
+        class: value.class,
+        instance: value.instance,
+        title: value.title,
+        window_type: value.window_type,
+        workspace: value.workspace.map(|workspace| workspace - 1),
+        screen: value.screen.map(|screen| screen - 1),
+        floating: value.floating.unwrap_or(false),
+        fullscreen: value.fullscreen.unwrap_or(false),
+        geometry: value.geometry,
+        no_border: value.no_border.unwrap_or(false),
+        focus: value.focus.unwrap_or(true),
+    })
+}
+
 impl TryFrom<UnresolvedActionEntry> for Action {
     type Error = ConfigError;
 
@@ -215,6 +417,31 @@ impl TryFrom<UnresolvedCommandEntry> for Command {
     }
 }
 
+impl TryFrom<UnresolvedMouseBindingEntry> for MouseBinding {
+    type Error = ConfigError;
+
+    fn try_from(value: UnresolvedMouseBindingEntry) -> Result<Self, Self::Error> {
+        if value.button.eq(&0) || value.button.gt(&5) {
+            return Err(ConfigError::MouseButton(format!(
+                "button = {}: mouse button must be between 1 and 5",
+                value.button
+            )));
+        }
+
+        Ok(MouseBinding {
+            button: value.button,
+            action: match value.action {
+                UnresolvedMouseAction::Move => MouseAction::Move,
+                UnresolvedMouseAction::Resize => MouseAction::Resize,
+            },
+            modifier: value
+                .modifiers
+                .into_iter()
+                .fold(0, |acc, modifier| acc + u32::from(modifier)),
+        })
+    }
+}
+
 impl TryFrom<String> for AutoCommand {
     type Error = ConfigError;
 
@@ -253,7 +480,17 @@ impl From<UnresolvedAction> for AvailableActions {
             UnresolvedAction::Quit => AvailableActions::Quit,
             UnresolvedAction::Close => AvailableActions::Close,
             UnresolvedAction::Reload => AvailableActions::Reload,
+            UnresolvedAction::Restart => AvailableActions::Restart,
             UnresolvedAction::Fullscreen => AvailableActions::Fullscreen,
+            UnresolvedAction::GrowMaster => AvailableActions::GrowMaster,
+            UnresolvedAction::ShrinkMaster => AvailableActions::ShrinkMaster,
+            UnresolvedAction::IncMaster => AvailableActions::IncMaster,
+            UnresolvedAction::DecMaster => AvailableActions::DecMaster,
+            UnresolvedAction::IncreaseGaps => AvailableActions::IncreaseGaps,
+            UnresolvedAction::DecreaseGaps => AvailableActions::DecreaseGaps,
+            UnresolvedAction::ToggleGaps => AvailableActions::ToggleGaps,
+            UnresolvedAction::ToggleFloating => AvailableActions::ToggleFloating,
+            UnresolvedAction::NextLayout => AvailableActions::NextLayout,
             UnresolvedAction::Workspace1 => AvailableActions::Workspace1,
             UnresolvedAction::Workspace2 => AvailableActions::Workspace2,
             UnresolvedAction::Workspace3 => AvailableActions::Workspace3,
@@ -272,6 +509,16 @@ impl From<UnresolvedAction> for AvailableActions {
             UnresolvedAction::MoveToWorkspace7 => AvailableActions::MoveToWorkspace7,
             UnresolvedAction::MoveToWorkspace8 => AvailableActions::MoveToWorkspace8,
             UnresolvedAction::MoveToWorkspace9 => AvailableActions::MoveToWorkspace9,
+            UnresolvedAction::FocusScreen1 => AvailableActions::FocusScreen1,
+            UnresolvedAction::FocusScreen2 => AvailableActions::FocusScreen2,
+            UnresolvedAction::FocusScreen3 => AvailableActions::FocusScreen3,
+            UnresolvedAction::FocusScreen4 => AvailableActions::FocusScreen4,
+            UnresolvedAction::FocusNextScreen => AvailableActions::FocusNextScreen,
+            UnresolvedAction::MoveToScreen1 => AvailableActions::MoveToScreen1,
+            UnresolvedAction::MoveToScreen2 => AvailableActions::MoveToScreen2,
+            UnresolvedAction::MoveToScreen3 => AvailableActions::MoveToScreen3,
+            UnresolvedAction::MoveToScreen4 => AvailableActions::MoveToScreen4,
+            UnresolvedAction::MoveToNextScreen => AvailableActions::MoveToNextScreen,
         }
     }
 }
@@ -299,3 +546,103 @@ impl From<&str> for Args {
         Self(args)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_errors_point_to_line_and_column() {
+        let source = "workspaces = 9\nleader = \"Mod1\"\nborder_width = \"wide\"\n";
+        let error = toml::from_str::<UnresolvedConfig>(source)
+            .err()
+            .expect("border width should not parse as a string");
+
+        match ConfigError::parse(error, source) {
+            ConfigError::Parse { line, column, .. } => assert_eq!((line, column), (3, 16)),
+            _ => panic!("expected a parse error"),
+        }
+    }
+
+    #[test]
+    fn fallback_config_can_reload_and_quit() {
+        let config = Config::fallback();
+        let actions = config
+            .actions()
+            .iter()
+            .map(|action| action.action())
+            .collect::<Vec<_>>();
+
+        assert!(actions
+            .iter()
+            .any(|action| matches!(action, AvailableActions::Reload)));
+        assert!(actions
+            .iter()
+            .any(|action| matches!(action, AvailableActions::Quit)));
+    }
+
+    #[test]
+    fn workspace_mode_defaults_to_one_set_per_screen() {
+        let source = r##"
+            workspaces = 9
+            leader = "Mod1"
+            border_color = "#252525"
+            active_border_color = "#2D4F67"
+            actions = []
+            commands = []
+        "##;
+        let parse = |source: &str| {
+            toml::from_str::<UnresolvedConfig>(source)
+                .ok()
+                .and_then(|config| Config::try_from(config).ok())
+                .expect("config should be valid")
+        };
+
+        let config = parse(source);
+        assert_eq!(config.workspace_mode(), WorkspaceMode::Screen);
+        assert_eq!(config.workspace_switch(), WorkspaceSwitch::Swap);
+
+        let config = parse(&format!(
+            "workspace_mode = \"global\"\nworkspace_switch = \"focus\"\n{source}"
+        ));
+        assert_eq!(config.workspace_mode(), WorkspaceMode::Global);
+        assert_eq!(config.workspace_switch(), WorkspaceSwitch::Focus);
+    }
+
+    #[test]
+    fn rules_are_resolved_and_matched() {
+        let rule = |source: &str| {
+            let rule = toml::from_str::<UnresolvedRuleEntry>(source).expect("rule should parse");
+            resolve_rule(rule, 9)
+        };
+
+        let resolved = rule("class = \"Firefox\"\nworkspace = 2\nscreen = 1")
+            .ok()
+            .expect("rule should be valid");
+        assert_eq!(resolved.workspace(), Some(1));
+        assert_eq!(resolved.screen(), Some(0));
+
+        assert!(matches!(rule("floating = true"), Err(ConfigError::Rule(_))));
+        assert!(matches!(
+            rule("class = \"a\"\nworkspace = 0"),
+            Err(ConfigError::Rule(_))
+        ));
+        assert!(matches!(
+            rule("class = \"a\"\nworkspace = 10"),
+            Err(ConfigError::Rule(_))
+        ));
+        assert!(rule("class = \"a\"\nworkspace = 9").is_ok());
+
+        let dialog = rule("window_type = \"Dialog\"")
+            .ok()
+            .expect("rule should be valid");
+        assert!(dialog.matches("", "", "", &["dialog".to_string()]));
+        assert!(!dialog.matches("", "", "", &["normal".to_string()]));
+
+        let title = rule("title = \"YouTube\"")
+            .ok()
+            .expect("rule should be valid");
+        assert!(title.matches("", "", "Music - YouTube - Firefox", &[]));
+        assert!(!title.matches("", "", "Music - youtube - Firefox", &[]));
+    }
+}
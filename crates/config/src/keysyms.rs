@@ -7,7 +7,7 @@ use crate::config_loader::ConfigError;
 
 /// map of all the keysyms available. you can see a full list here:
 /// https://www.cl.cam.ac.uk/~mgk25/ucs/keysymdef.h
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Keysym {
     XK_BackSpace = 0xff08,
     XK_Return = 0xff0d,
@@ -1,9 +1,14 @@
 mod color_parser;
 mod config;
+mod config_checker;
 mod config_loader;
 pub mod keysyms;
 
-pub use config::{AutoCommand, AvailableActions, Config};
+use anyhow::Context;
+pub use config::{
+    AutoCommand, AvailableActions, Config, MouseAction, WindowRule, WorkspaceMode, WorkspaceSwitch,
+};
+pub use config_checker::check_config;
 use config_loader::{ConfigError, UnresolvedConfig};
 use std::path::{Path, PathBuf};
 
@@ -50,43 +55,54 @@ where
     P: AsRef<Path>,
 {
     let config_file = std::fs::read_to_string(path.as_ref())?;
-    let config = toml::from_str::<UnresolvedConfig>(&config_file)?;
-    match Config::try_from(config) {
+    let config = toml::from_str::<UnresolvedConfig>(&config_file)
+        .map_err(|e| ConfigError::parse(e, &config_file));
+    match config.and_then(Config::try_from) {
         Ok(config) => Ok(config),
         Err(e) => match e {
+            ConfigError::Parse {
+                message,
+                line,
+                column,
+            } => anyhow::bail!("line {line}, column {column}: {message}"),
             ConfigError::Key(msg) => anyhow::bail!(msg),
             ConfigError::Workspaces(msg) => anyhow::bail!(msg),
             ConfigError::BorderWidth(msg) => anyhow::bail!(msg),
             ConfigError::BorderColor(msg) => anyhow::bail!(msg),
             ConfigError::InvalidCommand(msg) => anyhow::bail!(msg),
             ConfigError::Color(msg) => anyhow::bail!(msg),
+            ConfigError::MouseButton(msg) => anyhow::bail!(msg),
+            ConfigError::MasterRatio(msg) => anyhow::bail!(msg),
+            ConfigError::Rule(msg) => anyhow::bail!(msg),
         },
     }
 }
 
-/// Try to load the configuration from 3 places, in the following order:
+/// Path of the configuration file, looked up in the following order:
 ///
 /// * If set, `LUCKY_CONFIG` will be prioritized and the config will be loaded from there;
 /// * If not available, will attempt to load from `XDG_CONFIG_HOME/lucky/config.toml`;
 /// * If not available, will attempt to load from `HOME`/.config/lucky/config.toml;
-/// * If not present on any of the directories above, will load the default configuration;
-pub fn load_config() -> Config {
-    let config_path = match std::env::var(LUCKY_CONF_ENV_VAR) {
+pub fn config_path() -> Option<PathBuf> {
+    match std::env::var(LUCKY_CONF_ENV_VAR) {
         Ok(var) => {
             tracing::debug!("loading config file from $LUCKY_CONFIG: {var:?}");
             Some(PathBuf::from(&var).join(CONFIG_FILE))
         }
         Err(_) => get_config_dir_path(),
-    };
-    match config_path
-        .map(load_config_from_file)
-        .unwrap_or(Ok(Config::default()))
-    {
-        Ok(config) => config,
-        Err(e) => {
-            tracing::error!("{e:?}");
-            Config::default()
-        }
+    }
+}
+
+/// Try to load the configuration from `config_path`, if not present on any of the directories,
+/// will load the fallback configuration.
+///
+/// A configuration file that fails to load is an error, so the caller can decide whether to
+/// keep its current configuration or use the fallback one
+pub fn load_config() -> anyhow::Result<Config> {
+    match config_path().filter(|path| path.exists()) {
+        Some(path) => load_config_from_file(&path)
+            .with_context(|| format!("failed to load configuration from {}", path.display())),
+        None => Ok(Config::fallback()),
     }
 }
 
@@ -114,3 +130,8 @@ fn data_dir() -> anyhow::Result<PathBuf> {
 pub fn log_file() -> anyhow::Result<(PathBuf, String)> {
     Ok((data_dir()?, format!("{}.log", APP_NAME)))
 }
+
+/// file where the state is kept across restarts
+pub fn state_file() -> anyhow::Result<PathBuf> {
+    Ok(data_dir()?.join(format!("{}.state", APP_NAME)))
+}
@@ -0,0 +1,157 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+static LUCKY_SOCKET_ENV_VAR: &str = "LUCKY_SOCKET";
+static XDG_RUNTIME_DIR: &str = "XDG_RUNTIME_DIR";
+static DISPLAY_ENV_VAR: &str = "DISPLAY";
+
+/// Requests accepted by the window manager, each request is a single line of JSON, such as:
+///
+/// `{"type":"action","action":"FocusLeft"}` or `{"type":"get_workspaces"}`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Request {
+    /// executes an action, exactly as if its key binding was pressed. Actions are named like
+    /// in the configuration file, and are only parsed by the window manager
+    Action { action: String },
+    /// lists the workspaces of every screen
+    GetWorkspaces,
+    /// lists every managed client
+    GetClients,
+    /// lists every screen
+    GetScreens,
+    /// keeps the connection open, receiving an `Event` line whenever the state changes
+    Subscribe,
+}
+
+/// Responses sent back by the window manager, one line of JSON per request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Response {
+    Ok,
+    Error { message: String },
+    Workspaces { workspaces: Vec<WorkspaceInfo> },
+    Clients { clients: Vec<ClientInfo> },
+    Screens { screens: Vec<ScreenInfo> },
+}
+
+/// Events pushed to subscribed connections, one line of JSON per event, such as:
+///
+/// `{"event":"workspace_changed","screen":0,"workspace":2}`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    WorkspaceChanged {
+        screen: usize,
+        workspace: usize,
+    },
+    ClientMapped {
+        window: u32,
+    },
+    ClientUnmapped {
+        window: u32,
+    },
+    FocusChanged {
+        window: Option<u32>,
+    },
+    LayoutChanged {
+        screen: usize,
+        workspace: usize,
+        layout: String,
+    },
+    MonitorAdded {
+        screen: usize,
+    },
+    MonitorRemoved {
+        screen: usize,
+    },
+    ConfigReloaded,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceInfo {
+    pub id: u8,
+    pub name: String,
+    pub screen: usize,
+    pub layout: String,
+    pub active: bool,
+    pub clients: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClientInfo {
+    pub window: u32,
+    pub frame: u32,
+    pub screen: usize,
+    pub workspace: u8,
+    pub focused: bool,
+    pub floating: bool,
+    pub fullscreen: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreenInfo {
+    pub index: usize,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub active: bool,
+    pub active_workspace: usize,
+}
+
+/// Where the window manager listens for requests:
+///
+/// * If set, `LUCKY_SOCKET` will be used as the socket path;
+/// * Otherwise, the socket is placed at `XDG_RUNTIME_DIR/lucky-<display>.sock`, so instances
+///   running on different displays don't fight over the same socket;
+pub fn socket_path() -> anyhow::Result<PathBuf> {
+    if let Ok(path) = std::env::var(LUCKY_SOCKET_ENV_VAR) {
+        return Ok(PathBuf::from(path));
+    }
+
+    let display = std::env::var(DISPLAY_ENV_VAR).unwrap_or_default();
+    match std::env::var(XDG_RUNTIME_DIR) {
+        Ok(runtime_dir) => Ok(PathBuf::from(runtime_dir).join(socket_file(&display))),
+        Err(_) => anyhow::bail!("failed to get $XDG_RUNTIME_DIR environment variable"),
+    }
+}
+
+/// `:1.0` becomes `lucky-1.0.sock`, anything that can't be part of a file name is replaced
+fn socket_file(display: &str) -> String {
+    let display = display
+        .trim_start_matches(':')
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() || c.eq(&'.') {
+            true => c,
+            false => '_',
+        })
+        .collect::<String>();
+
+    format!("lucky-{display}.sock")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests_are_tagged_by_type() {
+        let request: Request =
+            serde_json::from_str(r#"{"type":"action","action":"FocusLeft"}"#).unwrap();
+        assert!(matches!(request, Request::Action { action } if action.eq("FocusLeft")));
+
+        let request: Request = serde_json::from_str(r#"{"type":"get_workspaces"}"#).unwrap();
+        assert!(matches!(request, Request::GetWorkspaces));
+    }
+
+    #[test]
+    fn socket_file_depends_on_display() {
+        assert_eq!(socket_file(":0"), "lucky-0.sock");
+        assert_eq!(socket_file(":1.0"), "lucky-1.0.sock");
+        assert_eq!(
+            socket_file("/tmp/launch/org.x:0"),
+            "lucky-_tmp_launch_org.x_0.sock"
+        );
+    }
+}
@@ -8,6 +8,10 @@ pub struct Atoms {
     pub net_wm_name: xcb::x::Atom,
     pub net_wm_state: xcb::x::Atom,
     pub net_wm_state_focused: xcb::x::Atom,
+    pub net_wm_state_fullscreen: xcb::x::Atom,
+    pub net_wm_state_above: xcb::x::Atom,
+    pub net_wm_state_hidden: xcb::x::Atom,
+    pub net_wm_state_demands_attention: xcb::x::Atom,
     pub net_wm_window_type: xcb::x::Atom,
     pub net_current_desktop: xcb::x::Atom,
     pub net_number_of_desktops: xcb::x::Atom,
@@ -21,6 +25,8 @@ pub struct Atoms {
     pub net_client_list: xcb::x::Atom,
     pub net_client_list_stacking: xcb::x::Atom,
     pub net_showing_desktop: xcb::x::Atom,
+    /// set on the root window with the reason the configuration file failed to load
+    pub lucky_config_error: xcb::x::Atom,
 }
 
 impl Atoms {
@@ -31,6 +37,11 @@ impl Atoms {
 
         let net_wm_state = Self::get_intern_atom(conn, b"_NET_WM_STATE");
         let net_wm_state_focused = Self::get_intern_atom(conn, b"_NET_WM_STATE_FOCUSED");
+        let net_wm_state_fullscreen = Self::get_intern_atom(conn, b"_NET_WM_STATE_FULLSCREEN");
+        let net_wm_state_above = Self::get_intern_atom(conn, b"_NET_WM_STATE_ABOVE");
+        let net_wm_state_hidden = Self::get_intern_atom(conn, b"_NET_WM_STATE_HIDDEN");
+        let net_wm_state_demands_attention =
+            Self::get_intern_atom(conn, b"_NET_WM_STATE_DEMANDS_ATTENTION");
 
         let net_supporting_wm_check = Self::get_intern_atom(conn, b"_NET_SUPPORTING_WM_CHECK");
 
@@ -46,6 +57,7 @@ impl Atoms {
         let net_client_list = Self::get_intern_atom(conn, b"_NET_CLIENT_LIST");
         let net_showing_desktop = Self::get_intern_atom(conn, b"_NET_SHOWING_DESKTOP");
         let net_client_list_stacking = Self::get_intern_atom(conn, b"_NET_CLIENT_LIST_STACKING");
+        let lucky_config_error = Self::get_intern_atom(conn, b"_LUCKY_CONFIG_ERROR");
 
         Atoms {
             wm_protocols,
@@ -53,6 +65,10 @@ impl Atoms {
             net_wm_name,
             net_wm_state,
             net_wm_state_focused,
+            net_wm_state_fullscreen,
+            net_wm_state_above,
+            net_wm_state_hidden,
+            net_wm_state_demands_attention,
             net_wm_window_type,
             net_client_list,
             net_current_desktop,
@@ -66,6 +82,7 @@ impl Atoms {
             net_supporting_wm_check,
             net_client_list_stacking,
             net_showing_desktop,
+            lucky_config_error,
         }
     }
 
@@ -83,6 +100,10 @@ impl Atoms {
             self.net_wm_name,
             self.net_wm_state,
             self.net_wm_state_focused,
+            self.net_wm_state_fullscreen,
+            self.net_wm_state_above,
+            self.net_wm_state_hidden,
+            self.net_wm_state_demands_attention,
             self.net_wm_window_type,
             self.net_current_desktop,
             self.net_number_of_desktops,
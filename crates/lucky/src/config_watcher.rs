@@ -0,0 +1,61 @@
+use crate::lucky::Message;
+use config::AvailableActions;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::time::{Duration, Instant, SystemTime};
+
+/// how often the configuration file is checked for changes
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+/// editors often write a file more than once when saving, so we wait for the file to settle
+/// before reloading
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Watches the configuration file, requesting a reload whenever it changes. The file is only
+/// watched while the watcher is alive, so it should only exist while `auto_reload` is enabled
+pub struct ConfigWatcher {
+    /// never sent through, dropping it is what stops the watching thread
+    _stop_tx: Sender<()>,
+}
+
+impl ConfigWatcher {
+    pub fn new(path: PathBuf, action_tx: Sender<Message>) -> Self {
+        let (stop_tx, stop_rx) = channel::<()>();
+        tracing::debug!("watching configuration file at {path:?}");
+
+        std::thread::spawn(move || {
+            let mut last_modified = modified(&path);
+            let mut changed_at: Option<Instant> = None;
+
+            while let Err(RecvTimeoutError::Timeout) = stop_rx.recv_timeout(POLL_INTERVAL) {
+                let modified = modified(&path);
+                if modified.ne(&last_modified) {
+                    last_modified = modified;
+                    changed_at = Some(Instant::now());
+                    continue;
+                }
+
+                if changed_at.is_some_and(|changed_at| changed_at.elapsed().ge(&DEBOUNCE)) {
+                    changed_at = None;
+                    if action_tx
+                        .send(Message::Action(AvailableActions::Reload))
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+            }
+
+            tracing::debug!("stopped watching configuration file at {path:?}");
+        });
+
+        ConfigWatcher { _stop_tx: stop_tx }
+    }
+}
+
+/// last time the file was modified, `None` while the file doesn't exist, which happens when
+/// editors replace the file instead of writing to it
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
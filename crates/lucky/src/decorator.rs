@@ -17,6 +17,12 @@ impl Decorator {
 
     pub fn decorate_client(&self, client: xcb::x::Window) -> anyhow::Result<xcb::x::Window> {
         let frame = self.create_frame()?;
+        // clients in the save set are given back to the root if we die, instead of being
+        // destroyed together with their frames
+        self.conn.send_request(&xcb::x::ChangeSaveSet {
+            mode: xcb::x::SetMode::Insert,
+            window: client,
+        });
         xcb_reparent_win!(self.conn, client, frame)?;
         Ok(frame)
     }
@@ -1,8 +1,8 @@
 use crate::{
     atoms::Atoms, decorator::Decorator, keyboard::Keyboard, layout_manager::LayoutManager,
-    screen_manager::ScreenManager,
+    lucky::Message, screen_manager::ScreenManager,
 };
-use config::{AvailableActions, Config};
+use config::Config;
 use std::{
     cell::RefCell,
     rc::Rc,
@@ -36,7 +36,7 @@ pub struct EventContext<'ec, E> {
     /// Channel where actions can be sent back to the window manager for actions that should affect
     /// global behavior, like `AvailableActions::Reload` for example. Which should reload the
     /// entire configuration for the window manager
-    pub action_tx: Sender<AvailableActions>,
+    pub action_tx: Sender<Message>,
 }
 
 impl Clone for EventContext<'_, xcb::x::KeyPressEvent> {
@@ -201,3 +201,114 @@ impl Clone for EventContext<'_, xcb::x::PropertyNotifyEvent> {
         }
     }
 }
+
+impl Clone for EventContext<'_, xcb::x::ClientMessageEvent> {
+    fn clone(&self) -> Self {
+        let event = xcb::x::ClientMessageEvent::new(
+            self.event.window(),
+            self.event.r#type(),
+            self.event.data(),
+        );
+
+        Self {
+            event,
+            conn: self.conn.clone(),
+            config: self.config.clone(),
+            keyboard: self.keyboard,
+            screen_manager: self.screen_manager.clone(),
+            atoms: self.atoms,
+            decorator: self.decorator,
+            layout_manager: self.layout_manager,
+            action_tx: self.action_tx.clone(),
+        }
+    }
+}
+
+impl Clone for EventContext<'_, xcb::x::ConfigureRequestEvent> {
+    fn clone(&self) -> Self {
+        let event = xcb::x::ConfigureRequestEvent::new(
+            self.event.stack_mode(),
+            self.event.parent(),
+            self.event.window(),
+            self.event.sibling(),
+            self.event.x(),
+            self.event.y(),
+            self.event.width(),
+            self.event.height(),
+            self.event.border_width(),
+            self.event.value_mask(),
+        );
+
+        Self {
+            event,
+            conn: self.conn.clone(),
+            config: self.config.clone(),
+            keyboard: self.keyboard,
+            screen_manager: self.screen_manager.clone(),
+            atoms: self.atoms,
+            decorator: self.decorator,
+            layout_manager: self.layout_manager,
+            action_tx: self.action_tx.clone(),
+        }
+    }
+}
+
+impl Clone for EventContext<'_, xcb::x::ButtonPressEvent> {
+    fn clone(&self) -> Self {
+        let event = xcb::x::ButtonPressEvent::new(
+            self.event.detail(),
+            self.event.time(),
+            self.event.root(),
+            self.event.event(),
+            self.event.child(),
+            self.event.root_x(),
+            self.event.root_y(),
+            self.event.event_x(),
+            self.event.event_y(),
+            self.event.state(),
+            self.event.same_screen(),
+        );
+
+        Self {
+            event,
+            conn: self.conn.clone(),
+            config: self.config.clone(),
+            keyboard: self.keyboard,
+            screen_manager: self.screen_manager.clone(),
+            atoms: self.atoms,
+            decorator: self.decorator,
+            layout_manager: self.layout_manager,
+            action_tx: self.action_tx.clone(),
+        }
+    }
+}
+
+impl Clone for EventContext<'_, xcb::x::MotionNotifyEvent> {
+    fn clone(&self) -> Self {
+        let event = xcb::x::MotionNotifyEvent::new(
+            self.event.detail(),
+            self.event.time(),
+            self.event.root(),
+            self.event.event(),
+            self.event.child(),
+            self.event.root_x(),
+            self.event.root_y(),
+            self.event.event_x(),
+            self.event.event_y(),
+            self.event.state(),
+            self.event.same_screen(),
+        );
+
+        Self {
+            event,
+            conn: self.conn.clone(),
+            config: self.config.clone(),
+            keyboard: self.keyboard,
+            screen_manager: self.screen_manager.clone(),
+            atoms: self.atoms,
+            decorator: self.decorator,
+            layout_manager: self.layout_manager,
+            action_tx: self.action_tx.clone(),
+        }
+    }
+}
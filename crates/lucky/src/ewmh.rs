@@ -43,11 +43,12 @@ pub fn ewmh_set_current_desktop(
 }
 
 /// updates _NET_DESKTOP_VIEWPORT atom, this is a list of pairs (x,y)
-/// of the starting position of each screen
-pub fn ewmh_set_desktop_viewport(
+/// of the starting position of each screen, or of the screen displaying each
+/// desktop when workspaces are shared between screens
+pub fn ewmh_set_desktop_viewport<'a>(
     conn: &Arc<xcb::Connection>,
     root: xcb::x::Window,
-    screens: &[Screen],
+    positions: impl Iterator<Item = &'a Position>,
     atoms: &Atoms,
 ) -> anyhow::Result<(), xcb::ProtocolError> {
     xcb_change_prop!(
@@ -56,9 +57,9 @@ pub fn ewmh_set_desktop_viewport(
         xcb::x::PropMode::Replace,
         xcb::x::ATOM_CARDINAL,
         atoms.net_desktop_viewport,
-        &screens.iter().fold(vec![], |mut acc, s| {
-            acc.push(s.position().x as u32);
-            acc.push(s.position().y as u32);
+        &positions.fold(vec![], |mut acc, position| {
+            acc.push(position.x as u32);
+            acc.push(position.y as u32);
             acc
         })
     )
@@ -168,6 +169,48 @@ pub fn ewmh_set_focus(
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum EwmhStateAction {
+    Add,
+    Remove,
+}
+
+/// adds or removes a single `_NET_WM_STATE_*` atom from the client window,
+/// leaving every other state already set on the window untouched
+pub fn ewmh_set_wm_state(
+    conn: &Arc<xcb::Connection>,
+    atoms: &Atoms,
+    window: xcb::x::Window,
+    state: xcb::x::Atom,
+    action: EwmhStateAction,
+) -> anyhow::Result<(), xcb::ProtocolError> {
+    match action {
+        EwmhStateAction::Add => {
+            // removing before appending ensures we never end up with the same
+            // state listed twice on the window
+            ewmh_remove_wm_state(conn, atoms, window, state)?;
+            xcb_change_prop!(
+                conn,
+                window,
+                xcb::x::PropMode::Append,
+                xcb::x::ATOM_ATOM,
+                atoms.net_wm_state,
+                &[state],
+            )
+        }
+        EwmhStateAction::Remove => ewmh_remove_wm_state(conn, atoms, window, state),
+    }
+}
+
+fn ewmh_remove_wm_state(
+    conn: &Arc<xcb::Connection>,
+    atoms: &Atoms,
+    window: xcb::x::Window,
+    state: xcb::x::Atom,
+) -> anyhow::Result<(), xcb::ProtocolError> {
+    xcb_remove_prop!(conn, window, xcb::x::ATOM_ATOM, atoms.net_wm_state, state)
+}
+
 /// list all the clients currently managed by the window manager
 /// by order of insertion
 pub fn ewmh_set_client_list<'a, I>(
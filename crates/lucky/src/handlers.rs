@@ -1,17 +1,24 @@
 mod action;
+mod client_message;
 mod command;
+mod configure_window;
 mod handler;
 mod hover;
 mod map_window;
+mod mouse;
 mod property_handler;
 mod unmap_window;
 
 use crate::event::EventContext;
 use action::ActionHandler;
+use client_message::ClientMessageHandler;
 use command::CommandHandler;
+use config::AvailableActions;
+use configure_window::ConfigureWindowHandler;
 use handler::Handler;
 use hover::HoverHandler;
 use map_window::MapWindowHandler;
+use mouse::MouseHandler;
 use property_handler::PropertyHandler;
 use unmap_window::UnmapWindowHandler;
 
@@ -30,6 +37,9 @@ impl Default for Handlers {
                 Box::<UnmapWindowHandler>::default(),
                 Box::<HoverHandler>::default(),
                 Box::<PropertyHandler>::default(),
+                Box::<ClientMessageHandler>::default(),
+                Box::<ConfigureWindowHandler>::default(),
+                Box::<MouseHandler>::default(),
             ],
         }
     }
@@ -48,6 +58,19 @@ impl Handlers {
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, err)]
+    pub fn on_action(
+        &mut self,
+        context: EventContext<xcb::x::KeyPressEvent>,
+        action: AvailableActions,
+    ) -> anyhow::Result<()> {
+        for handler in self.handlers.iter_mut() {
+            handler.on_action(context.clone(), action.clone())?;
+        }
+
+        Ok(())
+    }
+
     #[tracing::instrument(skip_all, err)]
     pub fn on_map_request(
         &mut self,
@@ -106,4 +129,64 @@ impl Handlers {
 
         Ok(())
     }
+
+    #[tracing::instrument(skip_all, err)]
+    pub fn on_client_message(
+        &mut self,
+        context: EventContext<xcb::x::ClientMessageEvent>,
+    ) -> anyhow::Result<()> {
+        for handler in self.handlers.iter_mut() {
+            handler.on_client_message(context.clone())?;
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, err)]
+    pub fn on_configure_request(
+        &mut self,
+        context: EventContext<xcb::x::ConfigureRequestEvent>,
+    ) -> anyhow::Result<()> {
+        for handler in self.handlers.iter_mut() {
+            handler.on_configure_request(context.clone())?;
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, err)]
+    pub fn on_button_press(
+        &mut self,
+        context: EventContext<xcb::x::ButtonPressEvent>,
+    ) -> anyhow::Result<()> {
+        for handler in self.handlers.iter_mut() {
+            handler.on_button_press(context.clone())?;
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, err)]
+    pub fn on_button_release(
+        &mut self,
+        context: EventContext<xcb::x::ButtonReleaseEvent>,
+    ) -> anyhow::Result<()> {
+        for handler in self.handlers.iter_mut() {
+            handler.on_button_release(context.clone())?;
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, err)]
+    pub fn on_motion_notify(
+        &mut self,
+        context: EventContext<xcb::x::MotionNotifyEvent>,
+    ) -> anyhow::Result<()> {
+        for handler in self.handlers.iter_mut() {
+            handler.on_motion_notify(context.clone())?;
+        }
+
+        Ok(())
+    }
 }
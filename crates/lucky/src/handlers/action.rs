@@ -1,9 +1,16 @@
 use crate::event::EventContext;
 use crate::handlers::handler::Handler;
+use crate::lucky::Message;
 use crate::screen_manager::Direction;
 use config::keysyms::Keysym;
 use config::AvailableActions;
 
+/// how much of the screen the master column grows or shrinks on each action
+const MASTER_RESIZE_STEP: f32 = 0.05;
+
+/// how many pixels the gaps grow or shrink on each action
+const GAPS_RESIZE_STEP: i32 = 2;
+
 #[derive(Default, Debug)]
 pub struct ActionHandler {}
 
@@ -15,43 +22,18 @@ impl Handler for ActionHandler {
             .key_get_one_sym(context.event.detail().into());
 
         if let Ok(keysym) = Keysym::try_from(keysym) {
-            if let Some(action) = context.config.borrow().actions().iter().find(|action| {
-                action.key().eq(&keysym) && context.event.state().eq(&action.modifiers().into())
-            }) {
-                use AvailableActions::*;
-
-                match action.action() {
-                    Quit => std::process::exit(1),
-                    Close => self.handle_close(&context)?,
-                    FocusLeft => self.handle_focus_client(&context, Direction::Left)?,
-                    FocusDown => self.handle_focus_client(&context, Direction::Down)?,
-                    FocusUp => self.handle_focus_client(&context, Direction::Up)?,
-                    FocusRight => self.handle_focus_client(&context, Direction::Right)?,
-                    MoveLeft => self.handle_move_client(&context, Direction::Left)?,
-                    MoveDown => self.handle_move_client(&context, Direction::Down)?,
-                    MoveUp => self.handle_move_client(&context, Direction::Up)?,
-                    MoveRight => self.handle_move_client(&context, Direction::Right)?,
-                    Reload => context.action_tx.send(action.action())?,
-                    Fullscreen => self.handle_fullscreen(&context)?,
-                    Workspace1 => self.handle_change_workspace(&context, action.action())?,
-                    Workspace2 => self.handle_change_workspace(&context, action.action())?,
-                    Workspace3 => self.handle_change_workspace(&context, action.action())?,
-                    Workspace4 => self.handle_change_workspace(&context, action.action())?,
-                    Workspace5 => self.handle_change_workspace(&context, action.action())?,
-                    Workspace6 => self.handle_change_workspace(&context, action.action())?,
-                    Workspace7 => self.handle_change_workspace(&context, action.action())?,
-                    Workspace8 => self.handle_change_workspace(&context, action.action())?,
-                    Workspace9 => self.handle_change_workspace(&context, action.action())?,
-                    MoveToWorkspace1 => self.handle_move_to_workspace(&context, action.action())?,
-                    MoveToWorkspace2 => self.handle_move_to_workspace(&context, action.action())?,
-                    MoveToWorkspace3 => self.handle_move_to_workspace(&context, action.action())?,
-                    MoveToWorkspace4 => self.handle_move_to_workspace(&context, action.action())?,
-                    MoveToWorkspace5 => self.handle_move_to_workspace(&context, action.action())?,
-                    MoveToWorkspace6 => self.handle_move_to_workspace(&context, action.action())?,
-                    MoveToWorkspace7 => self.handle_move_to_workspace(&context, action.action())?,
-                    MoveToWorkspace8 => self.handle_move_to_workspace(&context, action.action())?,
-                    MoveToWorkspace9 => self.handle_move_to_workspace(&context, action.action())?,
-                }
+            let action = context
+                .config
+                .borrow()
+                .actions()
+                .iter()
+                .find(|action| {
+                    action.key().eq(&keysym) && context.event.state().eq(&action.modifiers().into())
+                })
+                .map(|action| action.action());
+
+            if let Some(action) = action {
+                self.handle_action(&context, action)?;
             }
         }
 
@@ -62,9 +44,87 @@ impl Handler for ActionHandler {
 
         Ok(())
     }
+
+    fn on_action(
+        &mut self,
+        context: EventContext<xcb::x::KeyPressEvent>,
+        action: AvailableActions,
+    ) -> anyhow::Result<()> {
+        self.handle_action(&context, action)?;
+
+        context
+            .screen_manager
+            .borrow_mut()
+            .update_atoms(context.atoms, &context.conn);
+
+        Ok(())
+    }
 }
 
 impl ActionHandler {
+    fn handle_action(
+        &self,
+        context: &EventContext<xcb::x::KeyPressEvent>,
+        action: AvailableActions,
+    ) -> anyhow::Result<()> {
+        use AvailableActions::*;
+
+        match action {
+            Quit => std::process::exit(1),
+            Close => self.handle_close(context)?,
+            FocusLeft => self.handle_focus_client(context, Direction::Left)?,
+            FocusDown => self.handle_focus_client(context, Direction::Down)?,
+            FocusUp => self.handle_focus_client(context, Direction::Up)?,
+            FocusRight => self.handle_focus_client(context, Direction::Right)?,
+            MoveLeft => self.handle_move_client(context, Direction::Left)?,
+            MoveDown => self.handle_move_client(context, Direction::Down)?,
+            MoveUp => self.handle_move_client(context, Direction::Up)?,
+            MoveRight => self.handle_move_client(context, Direction::Right)?,
+            Reload => context.action_tx.send(Message::Action(action))?,
+            Restart => context.action_tx.send(Message::Action(action))?,
+            Fullscreen => self.handle_fullscreen(context)?,
+            GrowMaster => self.handle_resize_master(context, MASTER_RESIZE_STEP)?,
+            ShrinkMaster => self.handle_resize_master(context, -MASTER_RESIZE_STEP)?,
+            IncMaster => self.handle_change_master_count(context, 1)?,
+            DecMaster => self.handle_change_master_count(context, -1)?,
+            IncreaseGaps => self.handle_resize_gaps(context, GAPS_RESIZE_STEP)?,
+            DecreaseGaps => self.handle_resize_gaps(context, -GAPS_RESIZE_STEP)?,
+            ToggleGaps => self.handle_toggle_gaps(context)?,
+            ToggleFloating => self.handle_toggle_floating(context)?,
+            NextLayout => self.handle_next_layout(context)?,
+            Workspace1 => self.handle_change_workspace(context, action)?,
+            Workspace2 => self.handle_change_workspace(context, action)?,
+            Workspace3 => self.handle_change_workspace(context, action)?,
+            Workspace4 => self.handle_change_workspace(context, action)?,
+            Workspace5 => self.handle_change_workspace(context, action)?,
+            Workspace6 => self.handle_change_workspace(context, action)?,
+            Workspace7 => self.handle_change_workspace(context, action)?,
+            Workspace8 => self.handle_change_workspace(context, action)?,
+            Workspace9 => self.handle_change_workspace(context, action)?,
+            MoveToWorkspace1 => self.handle_move_to_workspace(context, action)?,
+            MoveToWorkspace2 => self.handle_move_to_workspace(context, action)?,
+            MoveToWorkspace3 => self.handle_move_to_workspace(context, action)?,
+            MoveToWorkspace4 => self.handle_move_to_workspace(context, action)?,
+            MoveToWorkspace5 => self.handle_move_to_workspace(context, action)?,
+            MoveToWorkspace6 => self.handle_move_to_workspace(context, action)?,
+            MoveToWorkspace7 => self.handle_move_to_workspace(context, action)?,
+            MoveToWorkspace8 => self.handle_move_to_workspace(context, action)?,
+            MoveToWorkspace9 => self.handle_move_to_workspace(context, action)?,
+            FocusScreen1 => self.handle_focus_screen(context, action)?,
+            FocusScreen2 => self.handle_focus_screen(context, action)?,
+            FocusScreen3 => self.handle_focus_screen(context, action)?,
+            FocusScreen4 => self.handle_focus_screen(context, action)?,
+            FocusNextScreen => self.handle_focus_screen(context, action)?,
+            MoveToScreen1 => self.handle_move_to_screen(context, action)?,
+            MoveToScreen2 => self.handle_move_to_screen(context, action)?,
+            MoveToScreen3 => self.handle_move_to_screen(context, action)?,
+            MoveToScreen4 => self.handle_move_to_screen(context, action)?,
+            MoveToNextScreen => self.handle_move_to_screen(context, action)?,
+        }
+
+        Ok(())
+    }
+
     fn handle_close(&self, context: &EventContext<xcb::x::KeyPressEvent>) -> anyhow::Result<()> {
         let mut screen_manager = context.screen_manager.borrow_mut();
         if let Some(client) = screen_manager.close_focused_client()? {
@@ -177,10 +237,137 @@ impl ActionHandler {
         }
     }
 
+    fn handle_focus_screen(
+        &self,
+        context: &EventContext<xcb::x::KeyPressEvent>,
+        action: AvailableActions,
+    ) -> anyhow::Result<()> {
+        match context.layout_manager.focus_screen(context, action) {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                tracing::error!("error while focusing screen {:?} ", context.event.event());
+                Err(e)
+            }
+        }
+    }
+
+    fn handle_move_to_screen(
+        &self,
+        context: &EventContext<xcb::x::KeyPressEvent>,
+        action: AvailableActions,
+    ) -> anyhow::Result<()> {
+        match context.layout_manager.move_to_screen(context, action) {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                tracing::error!(
+                    "error while moving client to screen {:?} ",
+                    context.event.event()
+                );
+                Err(e)
+            }
+        }
+    }
+
     fn handle_fullscreen(
         &self,
-        _context: &EventContext<xcb::x::KeyPressEvent>,
+        context: &EventContext<xcb::x::KeyPressEvent>,
     ) -> anyhow::Result<()> {
-        Ok(())
+        match context.layout_manager.toggle_fullscreen(context) {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                tracing::error!(
+                    "error while toggling fullscreen {:?} ",
+                    context.event.event()
+                );
+                Err(e)
+            }
+        }
+    }
+
+    fn handle_toggle_floating(
+        &self,
+        context: &EventContext<xcb::x::KeyPressEvent>,
+    ) -> anyhow::Result<()> {
+        match context.layout_manager.toggle_floating(context) {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                tracing::error!("error while toggling floating {:?} ", context.event.event());
+                Err(e)
+            }
+        }
+    }
+
+    fn handle_next_layout(
+        &self,
+        context: &EventContext<xcb::x::KeyPressEvent>,
+    ) -> anyhow::Result<()> {
+        match context.layout_manager.next_layout(context) {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                tracing::error!("error while changing layout {:?} ", context.event.event());
+                Err(e)
+            }
+        }
+    }
+
+    fn handle_resize_master(
+        &self,
+        context: &EventContext<xcb::x::KeyPressEvent>,
+        amount: f32,
+    ) -> anyhow::Result<()> {
+        match context.layout_manager.resize_master(context, amount) {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                tracing::error!(
+                    "error while resizing master area {:?} ",
+                    context.event.event()
+                );
+                Err(e)
+            }
+        }
+    }
+
+    fn handle_change_master_count(
+        &self,
+        context: &EventContext<xcb::x::KeyPressEvent>,
+        amount: isize,
+    ) -> anyhow::Result<()> {
+        match context.layout_manager.change_master_count(context, amount) {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                tracing::error!(
+                    "error while changing master count {:?} ",
+                    context.event.event()
+                );
+                Err(e)
+            }
+        }
+    }
+
+    fn handle_resize_gaps(
+        &self,
+        context: &EventContext<xcb::x::KeyPressEvent>,
+        amount: i32,
+    ) -> anyhow::Result<()> {
+        match context.layout_manager.resize_gaps(context, amount) {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                tracing::error!("error while resizing gaps {:?} ", context.event.event());
+                Err(e)
+            }
+        }
+    }
+
+    fn handle_toggle_gaps(
+        &self,
+        context: &EventContext<xcb::x::KeyPressEvent>,
+    ) -> anyhow::Result<()> {
+        match context.layout_manager.toggle_gaps(context) {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                tracing::error!("error while toggling gaps {:?} ", context.event.event());
+                Err(e)
+            }
+        }
     }
 }
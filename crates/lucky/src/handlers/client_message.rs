@@ -0,0 +1,155 @@
+use crate::event::EventContext;
+use crate::ewmh::{ewmh_set_wm_state, EwmhStateAction};
+use crate::handlers::handler::Handler;
+use xcb::Xid;
+
+/// The action a client is requesting on a `_NET_WM_STATE` client message, as defined on the
+/// EWMH spec section "_NET_WM_STATE"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WmStateRequest {
+    Remove,
+    Add,
+    Toggle,
+}
+
+impl TryFrom<u32> for WmStateRequest {
+    type Error = anyhow::Error;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(WmStateRequest::Remove),
+            1 => Ok(WmStateRequest::Add),
+            2 => Ok(WmStateRequest::Toggle),
+            _ => anyhow::bail!("invalid _NET_WM_STATE action {value}"),
+        }
+    }
+}
+
+impl WmStateRequest {
+    /// resolves the request against the current state, returning the new state
+    fn apply(&self, current: bool) -> bool {
+        match self {
+            WmStateRequest::Remove => false,
+            WmStateRequest::Add => true,
+            WmStateRequest::Toggle => !current,
+        }
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct ClientMessageHandler {}
+
+impl ClientMessageHandler {
+    /// Handles `_NET_WM_STATE` requests, a single message can change up to two properties at
+    /// once, which is how clients request for things like maximizing both vertically and
+    /// horizontally. Properties we don't support are silently ignored
+    fn handle_wm_state(
+        &self,
+        context: &EventContext<xcb::x::ClientMessageEvent>,
+        data: [u32; 5],
+    ) -> anyhow::Result<()> {
+        // any client can send us messages, so a malformed one should never take down the
+        // window manager
+        let request = match WmStateRequest::try_from(data[0]) {
+            Ok(request) => request,
+            Err(e) => {
+                tracing::error!("{e:?}");
+                return Ok(());
+            }
+        };
+        let window = context.event.window();
+
+        let Some(frame) = context
+            .screen_manager
+            .borrow()
+            .clients()
+            .values()
+            .find(|client| client.window.eq(&window))
+            .map(|client| client.frame)
+        else {
+            tracing::debug!("received _NET_WM_STATE for unmanaged window {window:?}");
+            return Ok(());
+        };
+
+        let atoms = context.atoms;
+        for &property in data[1..3].iter().filter(|&&property| property.ne(&0)) {
+            if property.eq(&atoms.net_wm_state_fullscreen.resource_id()) {
+                let fullscreen = context
+                    .screen_manager
+                    .borrow()
+                    .clients()
+                    .get(&frame)
+                    .is_some_and(|client| client.fullscreen);
+                context.layout_manager.set_fullscreen(
+                    &context.screen_manager,
+                    atoms,
+                    context.decorator,
+                    frame,
+                    request.apply(fullscreen),
+                )?;
+                continue;
+            }
+
+            let mut screen_manager = context.screen_manager.borrow_mut();
+            let Some(client) = screen_manager.clients_mut().get_mut(&frame) else {
+                continue;
+            };
+
+            let (state, enabled) = match property {
+                _ if property.eq(&atoms.net_wm_state_above.resource_id()) => {
+                    client.above = request.apply(client.above);
+                    (atoms.net_wm_state_above, client.above)
+                }
+                // hidden clients are still managed, but are not displayed until a client
+                // message asking to remove the hidden state arrives
+                _ if property.eq(&atoms.net_wm_state_hidden.resource_id()) => {
+                    client.visible = !request.apply(!client.visible);
+                    (atoms.net_wm_state_hidden, !client.visible)
+                }
+                _ if property.eq(&atoms.net_wm_state_demands_attention.resource_id()) => {
+                    client.demands_attention = request.apply(client.demands_attention);
+                    (
+                        atoms.net_wm_state_demands_attention,
+                        client.demands_attention,
+                    )
+                }
+                _ => {
+                    tracing::debug!("ignoring unsupported _NET_WM_STATE {property}");
+                    continue;
+                }
+            };
+
+            let action = match enabled {
+                true => EwmhStateAction::Add,
+                false => EwmhStateAction::Remove,
+            };
+            ewmh_set_wm_state(&context.conn, atoms, window, state, action).ok();
+        }
+
+        context
+            .layout_manager
+            .display_screens(&context.screen_manager, context.decorator)
+    }
+}
+
+impl Handler for ClientMessageHandler {
+    fn on_client_message(
+        &mut self,
+        context: EventContext<xcb::x::ClientMessageEvent>,
+    ) -> anyhow::Result<()> {
+        let xcb::x::ClientMessageData::Data32(data) = context.event.data() else {
+            return Ok(());
+        };
+
+        if context.event.r#type().eq(&context.atoms.net_wm_state) {
+            self.handle_wm_state(&context, data)?;
+        }
+
+        context
+            .screen_manager
+            .borrow_mut()
+            .update_atoms(context.atoms, &context.conn);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,274 @@
+use crate::event::EventContext;
+use crate::handlers::handler::Handler;
+use crate::position::Position;
+use crate::screen::Client;
+use crate::screen_manager::ScreenManager;
+use crate::xcb_utils::*;
+use anyhow::Context;
+use std::cell::RefCell;
+
+#[derive(Default, Debug)]
+pub struct ConfigureWindowHandler {}
+
+impl ConfigureWindowHandler {
+    /// Windows we don't manage are free to position themselves wherever they want, so we
+    /// forward exactly what was requested, following the order of `xcb::x::ConfigWindowMask`
+    fn apply_requested_geometry(&self, context: &EventContext<xcb::x::ConfigureRequestEvent>) {
+        let event = &context.event;
+        let mask = event.value_mask();
+        let mut value_list = vec![];
+
+        if mask.contains(xcb::x::ConfigWindowMask::X) {
+            value_list.push(xcb::x::ConfigWindow::X(event.x().into()));
+        }
+        if mask.contains(xcb::x::ConfigWindowMask::Y) {
+            value_list.push(xcb::x::ConfigWindow::Y(event.y().into()));
+        }
+        if mask.contains(xcb::x::ConfigWindowMask::WIDTH) {
+            value_list.push(xcb::x::ConfigWindow::Width(event.width().into()));
+        }
+        if mask.contains(xcb::x::ConfigWindowMask::HEIGHT) {
+            value_list.push(xcb::x::ConfigWindow::Height(event.height().into()));
+        }
+        if mask.contains(xcb::x::ConfigWindowMask::BORDER_WIDTH) {
+            value_list.push(xcb::x::ConfigWindow::BorderWidth(
+                event.border_width().into(),
+            ));
+        }
+        if mask.contains(xcb::x::ConfigWindowMask::SIBLING) {
+            value_list.push(xcb::x::ConfigWindow::Sibling(event.sibling()));
+        }
+        if mask.contains(xcb::x::ConfigWindowMask::STACK_MODE) {
+            value_list.push(xcb::x::ConfigWindow::StackMode(event.stack_mode()));
+        }
+
+        context.conn.send_request(&xcb::x::ConfigureWindow {
+            window: event.window(),
+            value_list: &value_list,
+        });
+    }
+
+    /// Where the frame of a floating client currently is, for clients configuring themselves
+    /// before we ever stored a floating position for them
+    fn frame_position(
+        &self,
+        context: &EventContext<xcb::x::ConfigureRequestEvent>,
+        client: &Client,
+    ) -> anyhow::Result<Position> {
+        let geometry = context
+            .conn
+            .wait_for_reply(context.conn.send_request(&xcb::x::GetGeometry {
+                drawable: xcb::x::Drawable::Window(client.frame),
+            }))
+            .context("failed to get frame geometry")?;
+        Ok(Position::new(
+            geometry.x().into(),
+            geometry.y().into(),
+            geometry.width().into(),
+            geometry.height().into(),
+        ))
+    }
+
+    /// Tiled clients are not allowed to change their own geometry, but ICCCM section 4.1.5
+    /// requires us to reply with a synthetic `ConfigureNotify` telling the client where it
+    /// actually is, in root coordinates
+    fn notify_real_geometry(
+        &self,
+        context: &EventContext<xcb::x::ConfigureRequestEvent>,
+        client: &Client,
+    ) -> anyhow::Result<()> {
+        let frame_geometry = context
+            .conn
+            .wait_for_reply(context.conn.send_request(&xcb::x::GetGeometry {
+                drawable: xcb::x::Drawable::Window(client.frame),
+            }))
+            .context("failed to get frame geometry")?;
+        let window_geometry = context
+            .conn
+            .wait_for_reply(context.conn.send_request(&xcb::x::GetGeometry {
+                drawable: xcb::x::Drawable::Window(client.window),
+            }))
+            .context("failed to get client geometry")?;
+
+        let border_width = frame_geometry.border_width() as i16;
+        let event = xcb::x::ConfigureNotifyEvent::new(
+            client.window,
+            client.window,
+            xcb::x::WINDOW_NONE,
+            frame_geometry.x() + border_width + window_geometry.x(),
+            frame_geometry.y() + border_width + window_geometry.y(),
+            window_geometry.width(),
+            window_geometry.height(),
+            0,
+            false,
+        );
+
+        xcb_send_event!(
+            context.conn,
+            xcb::x::SendEventDest::Window(client.window),
+            xcb::x::EventMask::STRUCTURE_NOTIFY,
+            &event
+        );
+
+        Ok(())
+    }
+}
+
+impl Handler for ConfigureWindowHandler {
+    fn on_configure_request(
+        &mut self,
+        context: EventContext<xcb::x::ConfigureRequestEvent>,
+    ) -> anyhow::Result<()> {
+        let window = context.event.window();
+        let client = context
+            .screen_manager
+            .borrow()
+            .clients()
+            .values()
+            .find(|client| client.window.eq(&window))
+            .cloned();
+
+        match client {
+            Some(client) => {
+                let border_width = client.border_width(&context.config.borrow());
+                let result = store_floating_geometry(
+                    &context.screen_manager,
+                    &client,
+                    &context.event,
+                    border_width,
+                    || self.frame_position(&context, &client),
+                )
+                .and_then(|floating| match floating {
+                    true => context
+                        .layout_manager
+                        .display_screens(&context.screen_manager, context.decorator),
+                    false => self.notify_real_geometry(&context, &client),
+                });
+
+                // the client might have been destroyed in the meantime, which is fine
+                if let Err(e) = result {
+                    tracing::error!("{e:?}");
+                }
+            }
+            None => self.apply_requested_geometry(&context),
+        }
+
+        Ok(())
+    }
+}
+
+/// Floating clients are allowed to move and resize themselves, so we store the requested
+/// geometry as their new floating position, keeping whatever was not requested. Returns
+/// whether the client is floating at all
+///
+/// The request describes the client window, which fills its frame, so only the position has
+/// to account for the frame border drawn around it
+fn store_floating_geometry(
+    screen_manager: &RefCell<ScreenManager>,
+    client: &Client,
+    event: &xcb::x::ConfigureRequestEvent,
+    border_width: u16,
+    frame_position: impl FnOnce() -> anyhow::Result<Position>,
+) -> anyhow::Result<bool> {
+    let is_floating = screen_manager.borrow().is_floating(client.frame);
+    if !is_floating {
+        return Ok(false);
+    }
+
+    let mask = event.value_mask();
+    let mut position = match client.floating_position.clone() {
+        Some(position) => position,
+        None => frame_position()?,
+    };
+
+    if mask.contains(xcb::x::ConfigWindowMask::X) {
+        position.x = i32::from(event.x()) - i32::from(border_width);
+    }
+    if mask.contains(xcb::x::ConfigWindowMask::Y) {
+        position.y = i32::from(event.y()) - i32::from(border_width);
+    }
+    if mask.contains(xcb::x::ConfigWindowMask::WIDTH) {
+        position.width = event.width().into();
+    }
+    if mask.contains(xcb::x::ConfigWindowMask::HEIGHT) {
+        position.height = event.height().into();
+    }
+
+    if let Some(client) = screen_manager
+        .borrow_mut()
+        .clients_mut()
+        .get_mut(&client.frame)
+    {
+        client.floating_position = Some(position);
+    }
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::screen::Screen;
+    use config::Config;
+    use std::rc::Rc;
+    use xcb::XidNew;
+
+    #[test]
+    fn configure_request_moves_floating_client() {
+        let config = Rc::new(RefCell::new(Config::default()));
+        let root = unsafe { xcb::x::Window::new(0) };
+        let screens = vec![Screen::new(
+            &config,
+            "DP-1",
+            Position::new(0, 0, 1920, 1080),
+        )];
+        let screen_manager = RefCell::new(ScreenManager::new(screens, config, root));
+
+        let (frame, window) = unsafe { (xcb::x::Window::new(1), xcb::x::Window::new(2)) };
+        screen_manager
+            .borrow_mut()
+            .create_client(frame, window, None, None);
+        {
+            let mut screen_manager = screen_manager.borrow_mut();
+            let client = screen_manager.clients_mut().get_mut(&frame).unwrap();
+            client.floating = true;
+            client.floating_position = Some(Position::new(10, 10, 300, 200));
+        }
+
+        let event = xcb::x::ConfigureRequestEvent::new(
+            xcb::x::StackMode::Above,
+            root,
+            window,
+            xcb::x::WINDOW_NONE,
+            100,
+            50,
+            640,
+            480,
+            0,
+            xcb::x::ConfigWindowMask::X | xcb::x::ConfigWindowMask::WIDTH,
+        );
+        let client = screen_manager.borrow().clients()[&frame].clone();
+        let floating = store_floating_geometry(&screen_manager, &client, &event, 2, || {
+            unreachable!("the floating position is already known")
+        })
+        .unwrap();
+
+        assert!(floating);
+        assert_eq!(
+            screen_manager.borrow().clients()[&frame].floating_position,
+            Some(Position::new(98, 10, 640, 200))
+        );
+
+        screen_manager
+            .borrow_mut()
+            .clients_mut()
+            .get_mut(&frame)
+            .unwrap()
+            .floating = false;
+        let floating = store_floating_geometry(&screen_manager, &client, &event, 2, || {
+            unreachable!("tiled clients are not moved")
+        })
+        .unwrap();
+        assert!(!floating);
+    }
+}
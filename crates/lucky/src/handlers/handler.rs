@@ -1,4 +1,5 @@
 use crate::event::EventContext;
+use config::AvailableActions;
 
 pub trait Handler: std::fmt::Debug {
     fn on_key_press(
@@ -8,6 +9,16 @@ pub trait Handler: std::fmt::Debug {
         Ok(())
     }
 
+    /// called when an action is requested without a key press, such as through the IPC socket,
+    /// in which case the event is a synthetic key press on the root window
+    fn on_action(
+        &mut self,
+        _context: EventContext<xcb::x::KeyPressEvent>,
+        _action: AvailableActions,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
     fn on_map_request(
         &mut self,
         _context: EventContext<xcb::x::MapRequestEvent>,
@@ -42,4 +53,39 @@ pub trait Handler: std::fmt::Debug {
     ) -> anyhow::Result<()> {
         Ok(())
     }
+
+    fn on_client_message(
+        &mut self,
+        _context: EventContext<xcb::x::ClientMessageEvent>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn on_configure_request(
+        &mut self,
+        _context: EventContext<xcb::x::ConfigureRequestEvent>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn on_button_press(
+        &mut self,
+        _context: EventContext<xcb::x::ButtonPressEvent>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn on_button_release(
+        &mut self,
+        _context: EventContext<xcb::x::ButtonReleaseEvent>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn on_motion_notify(
+        &mut self,
+        _context: EventContext<xcb::x::MotionNotifyEvent>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
 }
@@ -9,18 +9,49 @@ impl Handler for HoverHandler {
         &mut self,
         context: EventContext<xcb::x::EnterNotifyEvent>,
     ) -> anyhow::Result<()> {
-        if context.config.borrow().focus_follow_mouse() {
-            let window = context.event.event();
-            context.screen_manager.borrow_mut().focus_client(window);
+        let mut screen_manager = context.screen_manager.borrow_mut();
+        screen_manager.maybe_switch_screen(
+            context.event.root_x(),
+            context.event.root_y(),
+            &context.conn,
+            context.atoms,
+        );
+
+        // the root window is entered when the pointer leaves a client for an empty area
+        let window = context.event.event();
+        let should_focus =
+            context.config.borrow().focus_follow_mouse() && window.ne(&screen_manager.root());
+        if should_focus {
+            screen_manager.focus_client(window);
+        }
+        screen_manager.update_atoms(context.atoms, &context.conn);
+        drop(screen_manager);
+
+        if should_focus {
             context
                 .layout_manager
                 .display_screens(&context.screen_manager, context.decorator)?;
         }
 
-        context
-            .screen_manager
-            .borrow_mut()
-            .update_atoms(context.atoms, &context.conn);
+        Ok(())
+    }
+
+    /// motion is only reported on the root window while the pointer is over an empty area or
+    /// over clients that don't care about it, which is enough to follow the pointer between
+    /// screens without any clients
+    fn on_motion_notify(
+        &mut self,
+        context: EventContext<xcb::x::MotionNotifyEvent>,
+    ) -> anyhow::Result<()> {
+        let mut screen_manager = context.screen_manager.borrow_mut();
+        if context.event.event().eq(&screen_manager.root()) {
+            screen_manager.maybe_switch_screen(
+                context.event.root_x(),
+                context.event.root_y(),
+                &context.conn,
+                context.atoms,
+            );
+        }
 
         Ok(())
     }
@@ -1,14 +1,144 @@
 use crate::event::EventContext;
-use crate::ewmh::{ewmh_set_active_window, ewmh_set_focus, EwmhFocusAction};
+use crate::ewmh::{
+    ewmh_set_active_window, ewmh_set_focus, ewmh_set_wm_state, EwmhFocusAction, EwmhStateAction,
+};
 use crate::handlers::handler::Handler;
 use crate::position::Position;
+use crate::rules::WindowProperties;
 use crate::screen::ReservedClient;
 use anyhow::Context;
+use config::WindowRule;
 
 #[derive(Default, Debug)]
 pub struct MapWindowHandler {}
 
 impl MapWindowHandler {
+    /// `create_client` already placed the client as the rule says, but the window itself still
+    /// has to reflect what the layout won't touch
+    fn apply_rule_properties(
+        &self,
+        context: &EventContext<xcb::x::MapRequestEvent>,
+        rule: &WindowRule,
+        frame: xcb::x::Window,
+        window: xcb::x::Window,
+    ) {
+        if rule.no_border() {
+            context.conn.send_request(&xcb::x::ConfigureWindow {
+                window: frame,
+                value_list: &[xcb::x::ConfigWindow::BorderWidth(0)],
+            });
+        }
+
+        if rule.fullscreen() {
+            ewmh_set_wm_state(
+                &context.conn,
+                context.atoms,
+                window,
+                context.atoms.net_wm_state_fullscreen,
+                EwmhStateAction::Add,
+            )
+            .ok();
+        }
+    }
+
+    /// Floats a dialog like client, centered over the client it is transient for, or over its
+    /// screen when it has no parent we know of
+    fn float_client(
+        &self,
+        context: &EventContext<xcb::x::MapRequestEvent>,
+        frame: xcb::x::Window,
+        window: xcb::x::Window,
+        parent: Option<xcb::x::Window>,
+    ) {
+        let geometry = |window: xcb::x::Window| {
+            context
+                .conn
+                .wait_for_reply(context.conn.send_request(&xcb::x::GetGeometry {
+                    drawable: xcb::x::Drawable::Window(window),
+                }))
+                .map(|geometry| Position::from(&geometry))
+                .ok()
+        };
+        let Some(size) = geometry(window) else {
+            return;
+        };
+
+        let mut screen_manager = context.screen_manager.borrow_mut();
+        let parent_frame = parent.and_then(|parent| {
+            screen_manager
+                .clients()
+                .values()
+                .find(|client| client.window.eq(&parent) || client.frame.eq(&parent))
+                .map(|client| client.frame)
+        });
+        let area = parent_frame.and_then(geometry).or_else(|| {
+            screen_manager
+                .screens()
+                .iter()
+                .find(|screen| {
+                    screen
+                        .workspaces()
+                        .iter()
+                        .any(|workspace| workspace.clients().contains(&frame))
+                })
+                .map(|screen| screen.get_available_area())
+        });
+
+        let Some(client) = screen_manager.clients_mut().get_mut(&frame) else {
+            return;
+        };
+        client.floating = true;
+        if client.floating_position.is_none() {
+            client.floating_position = area.map(|area| area.centered(size.width, size.height));
+        }
+    }
+
+    /// Clients floating on their workspace keep the geometry they were created with, placing
+    /// the frame so the window itself stays where it asked to be. Windows that didn't ask for
+    /// any position are centered on their screen instead of all stacking at the origin
+    fn keep_own_geometry(
+        &self,
+        context: &EventContext<xcb::x::MapRequestEvent>,
+        frame: xcb::x::Window,
+        geometry: Option<Position>,
+    ) {
+        let Some(geometry) = geometry else {
+            return;
+        };
+
+        let mut screen_manager = context.screen_manager.borrow_mut();
+        if !screen_manager.is_floating(frame) {
+            return;
+        }
+        let area = screen_manager
+            .screens()
+            .iter()
+            .find(|screen| {
+                screen
+                    .workspaces()
+                    .iter()
+                    .any(|workspace| workspace.clients().contains(&frame))
+            })
+            .map(|screen| screen.get_available_area());
+
+        let Some(client) = screen_manager.clients_mut().get_mut(&frame) else {
+            return;
+        };
+        if client.floating_position.is_some() {
+            return;
+        }
+        let border_width = client.border_width(&context.config.borrow()) as i32;
+        client.floating_position = match (geometry.x, geometry.y, area) {
+            (0, 0, Some(area)) => Some(area.centered(geometry.width, geometry.height)),
+            _ => Some(Position::new(
+                geometry.x - border_width,
+                geometry.y - border_width,
+                geometry.width,
+                geometry.height,
+            )),
+        };
+    }
+
     fn setup_reserved_client(
         &self,
         values: &[u32],
@@ -71,7 +201,14 @@ impl MapWindowHandler {
                     height: right_end_y - right_start_y,
                 }
             }
-            _ => unreachable!(),
+            // docks and desktops that don't reserve any space keep their own geometry
+            _ => context
+                .conn
+                .wait_for_reply(context.conn.send_request(&xcb::x::GetGeometry {
+                    drawable: xcb::x::Drawable::Window(context.event.window()),
+                }))
+                .map(|geometry| Position::from(&geometry))
+                .unwrap_or(position),
         };
 
         let reserved_client = ReservedClient {
@@ -105,13 +242,24 @@ impl Handler for MapWindowHandler {
             long_length: 12,
         });
 
+        let properties = WindowProperties::new(&context.conn, context.atoms, window);
+
         // if this window is requesting to reserve space onscreen, so we have
         // to do a few things to ensure it is handled properly, the first is
         // that this should not be handled as a regular client, but as a
         // reserved client.
-        let is_reserving_space = context.conn.wait_for_reply(cookie).unwrap();
-        if let Some(values) = is_reserving_space.value::<u32>().get(0..12) {
-            self.setup_reserved_client(values, &context);
+        //
+        // docks and desktops that don't reserve space are handled the same way, just without
+        // reserving any area of the screen
+        //
+        // the window might also be gone by the time we handle its request, which is fine
+        let Ok(is_reserving_space) = context.conn.wait_for_reply(cookie) else {
+            tracing::debug!("window {window:?} was destroyed before being mapped");
+            return Ok(());
+        };
+        let reserved_values = is_reserving_space.value::<u32>().get(0..12);
+        if reserved_values.is_some() || properties.is_reserved() {
+            self.setup_reserved_client(reserved_values.unwrap_or(&[0; 12]), &context);
             context
                 .layout_manager
                 .display_screens(&context.screen_manager, context.decorator)
@@ -123,6 +271,18 @@ impl Handler for MapWindowHandler {
             return Ok(());
         }
 
+        let rule = properties.find_rule(&context.config.borrow());
+
+        // once reparented, the window geometry is relative to its frame, so we need to know
+        // where the window wanted to be beforehand
+        let geometry = context
+            .conn
+            .wait_for_reply(context.conn.send_request(&xcb::x::GetGeometry {
+                drawable: xcb::x::Drawable::Window(window),
+            }))
+            .map(|geometry| Position::from(&geometry))
+            .ok();
+
         let frame = context.decorator.decorate_client(window)?;
         let current_focused_client = context
             .screen_manager
@@ -140,28 +300,43 @@ impl Handler for MapWindowHandler {
             .enable_client_events(frame)
             .context("failed to enable events for frame")?;
 
-        context
-            .screen_manager
-            .borrow_mut()
-            .create_client(frame, window);
+        // clients placed on a hidden workspace or another screen are focused there, but must
+        // not steal the input focus from what is currently displayed
+        let should_focus = context.screen_manager.borrow_mut().create_client(
+            frame,
+            window,
+            rule.as_ref(),
+            properties.desktop,
+        );
 
-        current_focused_client.map(|client| {
-            ewmh_set_focus(
+        if let Some(rule) = rule.as_ref() {
+            self.apply_rule_properties(&context, rule, frame, window);
+        }
+
+        if properties.should_float() {
+            self.float_client(&context, frame, window, properties.transient_for);
+        }
+        self.keep_own_geometry(&context, frame, geometry);
+
+        if should_focus {
+            current_focused_client.map(|client| {
+                ewmh_set_focus(
+                    &context.conn,
+                    context.atoms,
+                    client.window,
+                    EwmhFocusAction::Unfocus,
+                )
+                .ok()
+            });
+            ewmh_set_focus(&context.conn, context.atoms, window, EwmhFocusAction::Focus).ok();
+            ewmh_set_active_window(
                 &context.conn,
+                context.screen_manager.borrow().root(),
                 context.atoms,
-                client.window,
-                EwmhFocusAction::Unfocus,
+                window,
             )
-            .ok()
-        });
-        ewmh_set_focus(&context.conn, context.atoms, window, EwmhFocusAction::Focus).ok();
-        ewmh_set_active_window(
-            &context.conn,
-            context.screen_manager.borrow().root(),
-            context.atoms,
-            window,
-        )
-        .ok();
+            .ok();
+        }
 
         context
             .layout_manager
@@ -0,0 +1,169 @@
+use crate::event::EventContext;
+use crate::handlers::handler::Handler;
+use crate::layout_manager::configure_window;
+use crate::position::Position;
+use anyhow::Context;
+use config::MouseAction;
+
+/// modifiers that can be used on `mouse_bindings`, every other bit on the event state, such as
+/// num lock or the pressed buttons, is ignored when matching a binding
+const BINDING_MODIFIERS_MASK: u32 = 0x00000001 | 0x00000004 | 0x00000008;
+
+/// smallest size a client can be resized to by dragging, so it never disappears
+const MIN_CLIENT_SIZE: i32 = 32;
+
+#[derive(Debug)]
+struct Drag {
+    frame: xcb::x::Window,
+    window: xcb::x::Window,
+    action: MouseAction,
+    /// pointer position on the root window when the drag started
+    pointer: (i16, i16),
+    /// frame position when the drag started
+    position: Position,
+}
+
+#[derive(Default, Debug)]
+pub struct MouseHandler {
+    drag: Option<Drag>,
+}
+
+impl Handler for MouseHandler {
+    fn on_button_press(
+        &mut self,
+        context: EventContext<xcb::x::ButtonPressEvent>,
+    ) -> anyhow::Result<()> {
+        let modifiers = context.event.state().bits() & BINDING_MODIFIERS_MASK;
+        let button = context.event.detail();
+        let Some(action) = context
+            .config
+            .borrow()
+            .mouse_bindings()
+            .iter()
+            .find(|binding| binding.button().eq(&button) && binding.modifiers().eq(&modifiers))
+            .map(|binding| binding.action())
+        else {
+            return Ok(());
+        };
+
+        // as the buttons are grabbed on the root window, the child is the top-level window
+        // under the cursor, which is the frame of the client
+        let target = context.event.child();
+        let Some(client) = context
+            .screen_manager
+            .borrow()
+            .clients()
+            .values()
+            .find(|client| client.frame.eq(&target) || client.window.eq(&target))
+            .cloned()
+        else {
+            return Ok(());
+        };
+
+        if client.fullscreen {
+            return Ok(());
+        }
+
+        let position = match client.floating_position {
+            Some(position) => position,
+            None => {
+                let geometry = context
+                    .conn
+                    .wait_for_reply(context.conn.send_request(&xcb::x::GetGeometry {
+                        drawable: xcb::x::Drawable::Window(client.frame),
+                    }))
+                    .context("failed to get frame geometry")?;
+                Position::new(
+                    geometry.x().into(),
+                    geometry.y().into(),
+                    geometry.width().into(),
+                    geometry.height().into(),
+                )
+            }
+        };
+
+        let mut screen_manager = context.screen_manager.borrow_mut();
+        if let Some(client) = screen_manager.clients_mut().get_mut(&client.frame) {
+            client.floating = true;
+            client.floating_position = Some(position.clone());
+        }
+        screen_manager.focus_client(client.frame);
+        drop(screen_manager);
+
+        self.drag = Some(Drag {
+            frame: client.frame,
+            window: client.window,
+            action,
+            pointer: (context.event.root_x(), context.event.root_y()),
+            position,
+        });
+
+        context
+            .layout_manager
+            .display_screens(&context.screen_manager, context.decorator)
+    }
+
+    fn on_motion_notify(
+        &mut self,
+        context: EventContext<xcb::x::MotionNotifyEvent>,
+    ) -> anyhow::Result<()> {
+        let Some(drag) = self.drag.as_ref() else {
+            return Ok(());
+        };
+
+        let delta_x = context.event.root_x() as i32 - drag.pointer.0 as i32;
+        let delta_y = context.event.root_y() as i32 - drag.pointer.1 as i32;
+        let mut position = drag.position.clone();
+
+        match drag.action {
+            MouseAction::Move => {
+                position.x += delta_x;
+                position.y += delta_y;
+            }
+            MouseAction::Resize => {
+                position.width = (position.width as i32 + delta_x).max(MIN_CLIENT_SIZE) as u32;
+                position.height = (position.height as i32 + delta_y).max(MIN_CLIENT_SIZE) as u32;
+            }
+        }
+
+        // only the dragged client changes, so we configure it directly instead of displaying
+        // every screen on each motion event
+        configure_window(&context.conn, drag.frame, position.clone());
+        configure_window(
+            &context.conn,
+            drag.window,
+            Position::new(0, 0, position.width, position.height),
+        );
+
+        if let Some(client) = context
+            .screen_manager
+            .borrow_mut()
+            .clients_mut()
+            .get_mut(&drag.frame)
+        {
+            client.floating_position = Some(position);
+        }
+
+        Ok(())
+    }
+
+    fn on_button_release(
+        &mut self,
+        context: EventContext<xcb::x::ButtonReleaseEvent>,
+    ) -> anyhow::Result<()> {
+        if self.drag.take().is_none() {
+            return Ok(());
+        }
+
+        context
+            .layout_manager
+            .display_screens(&context.screen_manager, context.decorator)?;
+
+        context
+            .screen_manager
+            .borrow_mut()
+            .update_atoms(context.atoms, &context.conn);
+
+        Ok(())
+    }
+}
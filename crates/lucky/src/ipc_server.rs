@@ -0,0 +1,417 @@
+use crate::lucky::Message;
+use crate::screen_manager::ScreenManager;
+use anyhow::Context;
+use ipc::{ClientInfo, Event, Request, Response, ScreenInfo, WorkspaceInfo};
+use std::{
+    collections::BTreeSet,
+    io::{BufRead, BufReader, Write},
+    os::unix::fs::FileTypeExt,
+    os::unix::net::{UnixListener, UnixStream},
+    sync::{
+        mpsc::{channel, Sender},
+        Arc, Mutex,
+    },
+};
+use xcb::Xid;
+
+/// Every connection subscribed to events, connections that are closed are only removed the
+/// next time an event is published
+pub type Subscribers = Arc<Mutex<Vec<Sender<Event>>>>;
+
+/// sends `event` to every subscribed connection, forgetting the ones that are gone
+pub fn publish(subscribers: &Subscribers, event: Event) {
+    let Ok(mut subscribers) = subscribers.lock() else {
+        return;
+    };
+    subscribers.retain(|subscriber| subscriber.send(event.clone()).is_ok());
+}
+
+/// The parts of the window manager state subscribers are notified about, events are
+/// published by comparing a snapshot taken before and after handling each event
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct StateSnapshot {
+    /// name of each screen, so replaced monitors are noticed even if their count is the same
+    screens: Vec<String>,
+    /// active workspace of each screen
+    active_workspaces: Vec<usize>,
+    /// layout of every workspace of each screen
+    layouts: Vec<Vec<String>>,
+    /// every managed client window
+    clients: BTreeSet<u32>,
+    focused: Option<u32>,
+}
+
+impl StateSnapshot {
+    pub fn new(screen_manager: &ScreenManager) -> Self {
+        StateSnapshot {
+            screens: screen_manager
+                .screens()
+                .iter()
+                .map(|screen| screen.name().to_string())
+                .collect(),
+            active_workspaces: screen_manager
+                .screens()
+                .iter()
+                .map(|screen| screen.active_workspace_id())
+                .collect(),
+            layouts: screen_manager
+                .screens()
+                .iter()
+                .map(|screen| {
+                    screen
+                        .workspaces()
+                        .iter()
+                        .map(|workspace| format!("{:?}", workspace.layout()))
+                        .collect()
+                })
+                .collect(),
+            clients: screen_manager
+                .clients()
+                .values()
+                .map(|client| client.window.resource_id())
+                .collect(),
+            focused: screen_manager
+                .get_focused_client()
+                .map(|client| client.window.resource_id()),
+        }
+    }
+
+    /// every event needed to go from this snapshot to `other`
+    pub fn changes(&self, other: &StateSnapshot) -> Vec<Event> {
+        let mut events = vec![];
+
+        events.extend(
+            self.screens
+                .iter()
+                .enumerate()
+                .filter(|(_, name)| !other.screens.contains(name))
+                .map(|(screen, _)| Event::MonitorRemoved { screen }),
+        );
+        events.extend(
+            other
+                .screens
+                .iter()
+                .enumerate()
+                .filter(|(_, name)| !self.screens.contains(name))
+                .map(|(screen, _)| Event::MonitorAdded { screen }),
+        );
+
+        for (screen, workspace) in other.active_workspaces.iter().enumerate() {
+            if self.active_workspaces.get(screen).ne(&Some(workspace)) {
+                events.push(Event::WorkspaceChanged {
+                    screen,
+                    workspace: *workspace,
+                });
+            }
+        }
+
+        for (screen, layouts) in other.layouts.iter().enumerate() {
+            for (workspace, layout) in layouts.iter().enumerate() {
+                let previous = self
+                    .layouts
+                    .get(screen)
+                    .and_then(|layouts| layouts.get(workspace));
+                if previous.is_some_and(|previous| previous.ne(layout)) {
+                    events.push(Event::LayoutChanged {
+                        screen,
+                        workspace,
+                        layout: layout.clone(),
+                    });
+                }
+            }
+        }
+
+        events.extend(
+            other
+                .clients
+                .difference(&self.clients)
+                .map(|&window| Event::ClientMapped { window }),
+        );
+        events.extend(
+            self.clients
+                .difference(&other.clients)
+                .map(|&window| Event::ClientUnmapped { window }),
+        );
+
+        if self.focused.ne(&other.focused) {
+            events.push(Event::FocusChanged {
+                window: other.focused,
+            });
+        }
+
+        events
+    }
+}
+
+/// A request received through the socket, along with where its response should be sent to
+#[derive(Debug)]
+pub struct IpcMessage {
+    pub request: Request,
+    pub reply_tx: Sender<Response>,
+}
+
+/// Binds the IPC socket and spawns a thread accepting connections on it. Every request is sent
+/// through `ipc_tx` to be handled by the main loop, as only it can touch the window manager state
+#[tracing::instrument(skip_all, err)]
+pub fn listen(ipc_tx: Sender<Message>, subscribers: Subscribers) -> anyhow::Result<()> {
+    let path = ipc::socket_path()?;
+
+    // a socket left behind by a previous instance would make binding fail, but we must not
+    // remove anything that isn't a socket, nor a socket someone is still listening on
+    if let Ok(metadata) = std::fs::symlink_metadata(&path) {
+        if !metadata.file_type().is_socket() {
+            anyhow::bail!("{} exists and is not a socket", path.display());
+        }
+        if UnixStream::connect(&path).is_ok() {
+            anyhow::bail!(
+                "another instance is already listening at {}",
+                path.display()
+            );
+        }
+        std::fs::remove_file(&path).context("failed to remove stale ipc socket")?;
+    }
+
+    let listener = UnixListener::bind(&path)
+        .context(format!("failed to bind ipc socket at {}", path.display()))?;
+    tracing::debug!("listening for ipc requests at {path:?}");
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(stream) = stream else {
+                continue;
+            };
+
+            let ipc_tx = ipc_tx.clone();
+            let subscribers = subscribers.clone();
+            std::thread::spawn(move || {
+                if let Err(e) = handle_connection(stream, ipc_tx, subscribers) {
+                    tracing::error!("{e:?}");
+                }
+            });
+        }
+    });
+
+    Ok(())
+}
+
+/// Reads line-delimited requests from a connection until it is closed, writing one response
+/// line for each of them
+fn handle_connection(
+    stream: UnixStream,
+    ipc_tx: Sender<Message>,
+    subscribers: Subscribers,
+) -> anyhow::Result<()> {
+    let mut writer = stream.try_clone().context("failed to clone ipc stream")?;
+    let reader = BufReader::new(stream);
+
+    for line in reader.lines() {
+        let line = line.context("failed to read from ipc stream")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<Request>(&line) {
+            Ok(Request::Subscribe) => return stream_events(writer, &subscribers),
+            Ok(request) => {
+                let (reply_tx, reply_rx) = channel();
+                ipc_tx
+                    .send(Message::Ipc(IpcMessage { request, reply_tx }))
+                    .context("failed to send ipc request through channel")?;
+                reply_rx
+                    .recv()
+                    .context("failed to receive ipc response from channel")?
+            }
+            Err(e) => Response::Error {
+                message: format!("invalid request: {e}"),
+            },
+        };
+
+        let response = serde_json::to_string(&response).context("failed to serialize response")?;
+        writeln!(writer, "{response}").context("failed to write to ipc stream")?;
+    }
+
+    Ok(())
+}
+
+/// Writes every published event to the connection until it is closed
+fn stream_events(mut writer: UnixStream, subscribers: &Subscribers) -> anyhow::Result<()> {
+    let (event_tx, event_rx) = channel();
+    subscribers
+        .lock()
+        .map_err(|_| anyhow::anyhow!("ipc subscribers lock was poisoned"))?
+        .push(event_tx);
+
+    let response = serde_json::to_string(&Response::Ok).context("failed to serialize response")?;
+    writeln!(writer, "{response}").context("failed to write to ipc stream")?;
+
+    for event in event_rx {
+        let event = serde_json::to_string(&event).context("failed to serialize event")?;
+        if writeln!(writer, "{event}").is_err() {
+            // the subscriber went away, which is how every subscription ends
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Every workspace of each screen, or each shared workspace once, on the screen holding it
+pub fn workspaces(screen_manager: &ScreenManager) -> Response {
+    let workspaces = screen_manager
+        .screens()
+        .iter()
+        .enumerate()
+        .flat_map(|(index, screen)| {
+            screen
+                .workspaces()
+                .iter()
+                .filter(move |workspace| {
+                    screen_manager
+                        .workspace_screen_idx(index, workspace.id())
+                        .eq(&index)
+                })
+                .map(move |workspace| WorkspaceInfo {
+                    id: workspace.id(),
+                    name: workspace.name().to_string(),
+                    screen: index,
+                    layout: format!("{:?}", workspace.layout()),
+                    active: screen.active_workspace_id().eq(&(workspace.id() as usize)),
+                    clients: workspace.clients().len(),
+                })
+        })
+        .collect();
+
+    Response::Workspaces { workspaces }
+}
+
+pub fn clients(screen_manager: &ScreenManager) -> Response {
+    let focused = screen_manager
+        .get_focused_client()
+        .map(|client| client.frame);
+
+    // clients are reported where they actually are, as screens and workspaces move them around
+    let location = |frame: &xcb::x::Window| {
+        screen_manager
+            .screens()
+            .iter()
+            .enumerate()
+            .find_map(|(index, screen)| {
+                screen
+                    .workspaces()
+                    .iter()
+                    .find(|workspace| workspace.clients().contains(frame))
+                    .map(|workspace| (index, workspace.id()))
+            })
+    };
+
+    let clients = screen_manager
+        .clients()
+        .values()
+        .filter_map(|client| Some((client, location(&client.frame)?)))
+        .map(|(client, (screen, workspace))| ClientInfo {
+            window: client.window.resource_id(),
+            frame: client.frame.resource_id(),
+            screen,
+            workspace,
+            focused: focused.is_some_and(|frame| frame.eq(&client.frame)),
+            floating: screen_manager.is_floating(client.frame),
+            fullscreen: client.fullscreen,
+        })
+        .collect();
+
+    Response::Clients { clients }
+}
+
+pub fn screens(screen_manager: &ScreenManager) -> Response {
+    let screens = screen_manager
+        .screens()
+        .iter()
+        .enumerate()
+        .map(|(index, screen)| ScreenInfo {
+            index,
+            x: screen.position().x,
+            y: screen.position().y,
+            width: screen.position().width,
+            height: screen.position().height,
+            active: screen_manager.active_screen_idx().eq(&index),
+            active_workspace: screen.active_workspace_id(),
+        })
+        .collect();
+
+    Response::Screens { screens }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::position::Position;
+    use crate::screen::Screen;
+    use config::Config;
+    use std::{cell::RefCell, rc::Rc};
+    use xcb::XidNew;
+
+    #[test]
+    fn snapshot_changes_become_events() {
+        let config = Rc::new(RefCell::new(Config::default()));
+        let root = unsafe { xcb::x::Window::new(0) };
+        let screens = vec![Screen::new(&config, "", Position::new(0, 0, 100, 100))];
+        let mut screen_manager = ScreenManager::new(screens, config, root);
+        let before = StateSnapshot::new(&screen_manager);
+
+        let (frame, window) = unsafe { (xcb::x::Window::new(1), xcb::x::Window::new(2)) };
+        screen_manager.create_client(frame, window, None, None);
+        screen_manager.screen_mut(0).set_active_workspace(3);
+
+        let events = before.changes(&StateSnapshot::new(&screen_manager));
+        assert!(events.contains(&Event::ClientMapped { window: 2 }));
+        assert!(events.contains(&Event::WorkspaceChanged {
+            screen: 0,
+            workspace: 3
+        }));
+        assert!(before.changes(&before).is_empty());
+    }
+
+    #[test]
+    fn replaced_monitors_become_events() {
+        let config = Rc::new(RefCell::new(Config::default()));
+        let root = unsafe { xcb::x::Window::new(0) };
+        let snapshot = |names: &[&str]| {
+            let screens = names
+                .iter()
+                .map(|name| Screen::new(&config, name, Position::new(0, 0, 100, 100)))
+                .collect();
+            StateSnapshot::new(&ScreenManager::new(screens, config.clone(), root))
+        };
+
+        let before = snapshot(&["DP-1", "DP-2"]);
+        let events = before.changes(&snapshot(&["DP-1", "HDMI-1"]));
+        assert!(events.contains(&Event::MonitorRemoved { screen: 1 }));
+        assert!(events.contains(&Event::MonitorAdded { screen: 1 }));
+        assert!(!events.contains(&Event::MonitorRemoved { screen: 0 }));
+
+        let events = before.changes(&snapshot(&["DP-2"]));
+        assert!(events.contains(&Event::MonitorRemoved { screen: 0 }));
+        assert!(!events.contains(&Event::MonitorRemoved { screen: 1 }));
+    }
+
+    #[test]
+    fn clients_are_reported_where_they_are() {
+        let config = Rc::new(RefCell::new(Config::default()));
+        let root = unsafe { xcb::x::Window::new(0) };
+        let screens = vec![
+            Screen::new(&config, "DP-1", Position::new(0, 0, 100, 100)),
+            Screen::new(&config, "DP-2", Position::new(100, 0, 100, 100)),
+        ];
+        let mut screen_manager = ScreenManager::new(screens, config, root);
+
+        let (frame, window) = unsafe { (xcb::x::Window::new(1), xcb::x::Window::new(2)) };
+        screen_manager.create_client(frame, window, None, None);
+        screen_manager.screen_mut(0).workspaces_mut()[0].remove_client(frame);
+        screen_manager.screen_mut(1).workspaces_mut()[3].new_client(frame);
+
+        let Response::Clients { clients } = clients(&screen_manager) else {
+            panic!("expected a list of clients");
+        };
+        assert_eq!((clients[0].screen, clients[0].workspace), (1, 3));
+    }
+}
@@ -1,10 +1,12 @@
 use config::Config;
 use std::{cell::RefCell, collections::HashMap, rc::Rc, sync::Arc};
-use xcb::x::{GrabKey, GrabMode, ModMask};
+use xcb::x::{GrabKey, GrabMode, ModMask, UngrabKey};
 use xkbcommon::xkb;
 
 pub struct Keyboard {
     pub state: xkbcommon::xkb::State,
+    /// keycode for every keysym name of the keymap, used to grab the configured keys
+    keycode_map: HashMap<&'static str, u32>,
 }
 
 impl Keyboard {
@@ -59,7 +61,38 @@ impl Keyboard {
             }
         });
 
-        for action in config.borrow().actions().iter() {
+        let keyboard = Keyboard { state, keycode_map };
+        keyboard.grab_keys(conn, &config.borrow(), root)?;
+
+        Ok(keyboard)
+    }
+
+    /// Releases every key we grabbed and grabs the keys of the current configuration, so
+    /// changes to bindings take effect without restarting
+    pub fn regrab_keys(
+        &self,
+        conn: &Arc<xcb::Connection>,
+        config: &Config,
+        root: xcb::x::Window,
+    ) -> anyhow::Result<()> {
+        conn.check_request(conn.send_request_checked(&UngrabKey {
+            key: xcb::x::GRAB_ANY,
+            grab_window: root,
+            modifiers: ModMask::ANY,
+        }))?;
+
+        self.grab_keys(conn, config, root)
+    }
+
+    fn grab_keys(
+        &self,
+        conn: &Arc<xcb::Connection>,
+        config: &Config,
+        root: xcb::x::Window,
+    ) -> anyhow::Result<()> {
+        let keycode_map = &self.keycode_map;
+
+        for action in config.actions().iter() {
             let keycode = match keycode_map.get(action.key().canonical_name()) {
                 Some(e) => *e as u8,
                 None => {
@@ -70,7 +103,7 @@ impl Keyboard {
             grab_key(conn.clone(), action.modifiers().inner(), keycode, root);
         }
 
-        for command in config.borrow().commands().iter() {
+        for command in config.commands().iter() {
             let keycode = match keycode_map.get(command.key().canonical_name()) {
                 Some(e) => *e as u8,
                 None => {
@@ -81,7 +114,7 @@ impl Keyboard {
             grab_key(conn.clone(), command.modifiers(), keycode, root);
         }
 
-        Ok(Keyboard { state })
+        Ok(())
     }
 }
 
@@ -1,15 +1,22 @@
+mod floating_layout;
+mod layout;
+mod monocle_layout;
 mod tall_layout;
-use crate::ewmh::{ewmh_set_active_window, ewmh_set_focus, EwmhFocusAction};
+use crate::ewmh::{
+    ewmh_set_active_window, ewmh_set_focus, ewmh_set_wm_state, EwmhFocusAction, EwmhStateAction,
+};
 
 use crate::xcb_utils::*;
 use crate::{
     atoms::Atoms,
     decorator::Decorator,
     event::EventContext,
-    layout_manager::tall_layout::TallLayout,
-    screen::{Workspace, WorkspaceLayout},
+    layout_manager::layout::Layout,
+    position::Position,
+    screen::{Client, Screen, Workspace},
     screen_manager::{Direction, ScreenManager},
 };
+use anyhow::Context;
 use config::{AvailableActions, Config};
 use std::{cell::RefCell, rc::Rc, sync::Arc};
 use xcb::Xid;
@@ -47,8 +54,16 @@ impl LayoutManager {
             let workspace = screen.active_workspace();
             let screen_manager = screen_manager.borrow();
 
-            let visible_clients = screen_manager
-                .get_visible_screen_clients(screen)
+            let workspace_clients = screen_manager.get_visible_screen_clients(screen);
+
+            // hidden clients keep their place on the workspace, but their frames are
+            // unmapped until they are requested to be shown again
+            workspace_clients
+                .iter()
+                .filter(|client| !client.visible)
+                .for_each(|client| self.hide_client(&client.frame));
+
+            let visible_clients = workspace_clients
                 .into_iter()
                 .filter(|client| client.visible)
                 .collect::<Vec<_>>();
@@ -60,21 +75,398 @@ impl LayoutManager {
 
             let focused_client = screen_manager.get_focused_client();
 
-            match workspace.layout() {
-                WorkspaceLayout::Tall => TallLayout::display_clients(
-                    &self.conn,
-                    &self.config,
-                    screen,
-                    visible_clients,
-                    focused_client,
-                    decorator,
-                )?,
+            // fullscreen and floating clients are taken out of the layout, so the
+            // remaining clients are arranged as if they were not there at all
+            let (fullscreen_clients, visible_clients): (Vec<_>, Vec<_>) = visible_clients
+                .into_iter()
+                .partition(|client| client.fullscreen);
+            let (floating_clients, tiled_clients): (Vec<_>, Vec<_>) = visible_clients
+                .into_iter()
+                .partition(|client| screen_manager.is_floating(client.frame));
+            let above_clients = tiled_clients
+                .iter()
+                .chain(floating_clients.iter())
+                .filter(|client| client.above)
+                .map(|client| client.frame)
+                .collect::<Vec<_>>();
+
+            self.display_reserved_clients(screen);
+
+            self.display_tiled_clients(
+                workspace.layout().as_layout(),
+                screen,
+                tiled_clients,
+                focused_client,
+                decorator,
+            )?;
+
+            for client in floating_clients {
+                self.display_floating_client(screen, client, focused_client, decorator)?;
+            }
+
+            for frame in above_clients {
+                self.conn.send_request(&xcb::x::ConfigureWindow {
+                    window: frame,
+                    value_list: &[xcb::x::ConfigWindow::StackMode(xcb::x::StackMode::Above)],
+                });
+            }
+
+            for client in fullscreen_clients {
+                self.display_fullscreen_client(screen, client, focused_client, decorator)?;
             }
         }
 
         Ok(())
     }
 
+    /// Applies the border width and color of the configuration to every frame, clients that
+    /// are visible get their proper colors back when they are displayed
+    pub fn redecorate_clients(
+        &self,
+        screen_manager: &ScreenManager,
+        decorator: &Decorator,
+    ) -> anyhow::Result<()> {
+        for client in screen_manager.clients().values() {
+            if !client.fullscreen {
+                self.conn.send_request(&xcb::x::ConfigureWindow {
+                    window: client.frame,
+                    value_list: &[xcb::x::ConfigWindow::BorderWidth(
+                        client.border_width(&self.config.borrow()).into(),
+                    )],
+                });
+            }
+
+            decorator
+                .unfocus_client(client)
+                .context("failed to redecorate client")?;
+        }
+
+        Ok(())
+    }
+
+    /// Displays the tiled clients of a screen where its layout placed them
+    fn display_tiled_clients(
+        &self,
+        layout: &dyn Layout,
+        screen: &Screen,
+        clients: Vec<&Client>,
+        focused_client: Option<&Client>,
+        decorator: &Decorator,
+    ) -> anyhow::Result<()> {
+        let placements = layout.arrange(screen, &clients, &self.config.borrow());
+
+        for (client, placement) in clients.iter().zip(placements) {
+            decorator
+                .unfocus_client(client)
+                .context("failed to unfocus client")?;
+
+            configure_window(&self.conn, client.frame, placement.frame);
+            configure_window(&self.conn, client.window, placement.window);
+            self.conn.send_request(&xcb::x::ConfigureWindow {
+                window: client.frame,
+                value_list: &[xcb::x::ConfigWindow::BorderWidth(
+                    client.border_width(&self.config.borrow()).into(),
+                )],
+            });
+
+            xcb_map_win!(self.conn, client.window);
+            xcb_map_win!(self.conn, client.frame);
+
+            if placement.raise {
+                self.conn.send_request(&xcb::x::ConfigureWindow {
+                    window: client.frame,
+                    value_list: &[xcb::x::ConfigWindow::StackMode(xcb::x::StackMode::Above)],
+                });
+            }
+        }
+
+        let Some(focused_client) = focused_client else {
+            return Ok(());
+        };
+
+        clients
+            .iter()
+            .find(|&&client| client == focused_client)
+            .map(|client| decorator.focus_client(client));
+
+        Ok(())
+    }
+
+    fn display_reserved_clients(&self, screen: &Screen) {
+        for client in screen.reserved_clients() {
+            configure_window(&self.conn, client.window, client.position.clone());
+            xcb_map_win!(self.conn, client.window);
+        }
+    }
+
+    /// Floating clients are displayed with their own geometry, stacked above every tiled client
+    fn display_floating_client(
+        &self,
+        screen: &Screen,
+        client: &Client,
+        focused_client: Option<&Client>,
+        decorator: &Decorator,
+    ) -> anyhow::Result<()> {
+        let position = client
+            .floating_position
+            .clone()
+            .unwrap_or_else(|| default_floating_position(screen));
+
+        self.conn.send_request(&xcb::x::ConfigureWindow {
+            window: client.frame,
+            value_list: &[
+                xcb::x::ConfigWindow::X(position.x),
+                xcb::x::ConfigWindow::Y(position.y),
+                xcb::x::ConfigWindow::Width(position.width),
+                xcb::x::ConfigWindow::Height(position.height),
+                xcb::x::ConfigWindow::BorderWidth(
+                    client.border_width(&self.config.borrow()).into(),
+                ),
+                xcb::x::ConfigWindow::StackMode(xcb::x::StackMode::Above),
+            ],
+        });
+        configure_window(
+            &self.conn,
+            client.window,
+            Position::new(0, 0, position.width, position.height),
+        );
+
+        xcb_map_win!(self.conn, client.window);
+        xcb_map_win!(self.conn, client.frame);
+
+        match focused_client.is_some_and(|focused| focused.eq(client)) {
+            true => decorator.focus_client(client),
+            false => decorator.unfocus_client(client),
+        }
+        .context("failed to decorate floating client")
+    }
+
+    /// Fullscreen clients cover the entire screen, ignoring any reserved area, and are stacked
+    /// above every other window, including reserved clients such as status bars
+    fn display_fullscreen_client(
+        &self,
+        screen: &Screen,
+        client: &Client,
+        focused_client: Option<&Client>,
+        decorator: &Decorator,
+    ) -> anyhow::Result<()> {
+        let position = screen.position();
+
+        self.conn.send_request(&xcb::x::ConfigureWindow {
+            window: client.frame,
+            value_list: &[
+                xcb::x::ConfigWindow::X(position.x),
+                xcb::x::ConfigWindow::Y(position.y),
+                xcb::x::ConfigWindow::Width(position.width),
+                xcb::x::ConfigWindow::Height(position.height),
+                xcb::x::ConfigWindow::BorderWidth(0),
+                xcb::x::ConfigWindow::StackMode(xcb::x::StackMode::Above),
+            ],
+        });
+        self.conn.send_request(&xcb::x::ConfigureWindow {
+            window: client.window,
+            value_list: &[
+                xcb::x::ConfigWindow::X(0),
+                xcb::x::ConfigWindow::Y(0),
+                xcb::x::ConfigWindow::Width(position.width),
+                xcb::x::ConfigWindow::Height(position.height),
+            ],
+        });
+
+        xcb_map_win!(self.conn, client.window);
+        xcb_map_win!(self.conn, client.frame);
+
+        if focused_client.is_some_and(|focused| focused.eq(client)) {
+            decorator
+                .focus_client(client)
+                .context("failed to focus fullscreen client")?;
+        }
+
+        Ok(())
+    }
+
+    /// Toggles the focused client between its slot on the layout and covering its whole screen
+    pub fn toggle_fullscreen(
+        &self,
+        context: &EventContext<xcb::x::KeyPressEvent>,
+    ) -> anyhow::Result<()> {
+        let Some((frame, fullscreen)) = context
+            .screen_manager
+            .borrow()
+            .get_focused_client()
+            .map(|client| (client.frame, client.fullscreen))
+        else {
+            return Ok(());
+        };
+
+        self.set_fullscreen(
+            &context.screen_manager,
+            context.atoms,
+            context.decorator,
+            frame,
+            !fullscreen,
+        )
+    }
+
+    /// Sets whether a client should be displayed fullscreen, keeping `_NET_WM_STATE_FULLSCREEN`
+    /// in sync so the client itself knows about the change
+    pub fn set_fullscreen(
+        &self,
+        screen_manager: &Rc<RefCell<ScreenManager>>,
+        atoms: &Atoms,
+        decorator: &Decorator,
+        frame: xcb::x::Window,
+        fullscreen: bool,
+    ) -> anyhow::Result<()> {
+        let mut sm = screen_manager.borrow_mut();
+        let client = sm
+            .clients_mut()
+            .get_mut(&frame)
+            .context("tried to set fullscreen on a client that was not on our list")?;
+
+        if client.fullscreen.eq(&fullscreen) {
+            return Ok(());
+        }
+
+        client.fullscreen = fullscreen;
+        let window = client.window;
+        let border_width = client.border_width(&self.config.borrow());
+        drop(sm);
+
+        let action = match fullscreen {
+            true => EwmhStateAction::Add,
+            false => EwmhStateAction::Remove,
+        };
+        ewmh_set_wm_state(
+            &self.conn,
+            atoms,
+            window,
+            atoms.net_wm_state_fullscreen,
+            action,
+        )
+        .ok();
+
+        // the layout only resizes frames, so we have to give back the border we took
+        // away when the client went fullscreen
+        if !fullscreen {
+            self.conn.send_request(&xcb::x::ConfigureWindow {
+                window: frame,
+                value_list: &[xcb::x::ConfigWindow::BorderWidth(border_width.into())],
+            });
+        }
+
+        self.display_screens(screen_manager, decorator)
+    }
+
+    /// Toggles whether the focused client floats above the layout. A client that starts
+    /// floating keeps the geometry it had on the layout, so it doesn't jump around the screen
+    pub fn toggle_floating(
+        &self,
+        context: &EventContext<xcb::x::KeyPressEvent>,
+    ) -> anyhow::Result<()> {
+        let mut screen_manager = context.screen_manager.borrow_mut();
+        let Some(frame) = screen_manager
+            .get_focused_client()
+            .map(|client| client.frame)
+        else {
+            return Ok(());
+        };
+
+        let geometry = self
+            .conn
+            .wait_for_reply(self.conn.send_request(&xcb::x::GetGeometry {
+                drawable: xcb::x::Drawable::Window(frame),
+            }))
+            .ok();
+
+        let client = screen_manager
+            .clients_mut()
+            .get_mut(&frame)
+            .context("focused client should be on our list")?;
+        client.floating = !client.floating;
+
+        if client.floating && client.floating_position.is_none() {
+            client.floating_position = geometry.as_ref().map(Position::from);
+        }
+
+        drop(screen_manager);
+        self.display_screens(&context.screen_manager, context.decorator)
+    }
+
+    /// Grows or shrinks the master column of the active workspace on the active screen
+    pub fn resize_master(
+        &self,
+        context: &EventContext<xcb::x::KeyPressEvent>,
+        amount: f32,
+    ) -> anyhow::Result<()> {
+        let mut screen_manager = context.screen_manager.borrow_mut();
+        let index = screen_manager.active_screen_idx();
+        screen_manager
+            .screen_mut(index)
+            .active_workspace_mut()
+            .resize_master(amount);
+
+        drop(screen_manager);
+        self.display_screens(&context.screen_manager, context.decorator)
+    }
+
+    /// Changes how many clients share the master column of the active workspace
+    pub fn change_master_count(
+        &self,
+        context: &EventContext<xcb::x::KeyPressEvent>,
+        amount: isize,
+    ) -> anyhow::Result<()> {
+        let mut screen_manager = context.screen_manager.borrow_mut();
+        let index = screen_manager.active_screen_idx();
+        screen_manager
+            .screen_mut(index)
+            .active_workspace_mut()
+            .change_master_count(amount);
+
+        drop(screen_manager);
+        self.display_screens(&context.screen_manager, context.decorator)
+    }
+
+    /// Grows or shrinks the gaps of the active workspace by `amount` pixels
+    pub fn resize_gaps(
+        &self,
+        context: &EventContext<xcb::x::KeyPressEvent>,
+        amount: i32,
+    ) -> anyhow::Result<()> {
+        let mut screen_manager = context.screen_manager.borrow_mut();
+        let index = screen_manager.active_screen_idx();
+        let screen = screen_manager.screen_mut(index);
+        let max_gap = screen.max_gap();
+        screen.active_workspace_mut().resize_gaps(amount, max_gap);
+
+        drop(screen_manager);
+        self.display_screens(&context.screen_manager, context.decorator)
+    }
+
+    /// Enables or disables the gaps of the active workspace
+    pub fn toggle_gaps(&self, context: &EventContext<xcb::x::KeyPressEvent>) -> anyhow::Result<()> {
+        let mut screen_manager = context.screen_manager.borrow_mut();
+        let index = screen_manager.active_screen_idx();
+        screen_manager
+            .screen_mut(index)
+            .active_workspace_mut()
+            .toggle_gaps();
+
+        drop(screen_manager);
+        self.display_screens(&context.screen_manager, context.decorator)
+    }
+
+    /// Switches the layout of the active workspace on the active screen to the next one
+    pub fn next_layout(&self, context: &EventContext<xcb::x::KeyPressEvent>) -> anyhow::Result<()> {
+        let mut screen_manager = context.screen_manager.borrow_mut();
+        let index = screen_manager.active_screen_idx();
+        let workspace = screen_manager.screen_mut(index).active_workspace_mut();
+        workspace.set_layout(workspace.layout().next());
+
+        drop(screen_manager);
+        self.display_screens(&context.screen_manager, context.decorator)
+    }
+
     #[tracing::instrument(skip_all, err)]
     pub fn change_focus(
         &self,
@@ -86,41 +478,11 @@ impl LayoutManager {
         let screen = screen_manager.screen(active_screen_idx);
         let workspace = screen.active_workspace();
 
-        let result = match workspace.layout() {
-            WorkspaceLayout::Tall => TallLayout::focus_client(&mut screen_manager, direction)?,
-        };
+        let layout = workspace.layout().as_layout();
+        let result = layout.focus_client(&mut screen_manager, direction)?;
 
         if let Some((prev_client, curr_client)) = result {
-            let prev_client =
-                prev_client.map(|client| screen_manager.clients().get(&client).unwrap());
-            let curr_client =
-                curr_client.map(|client| screen_manager.clients().get(&client).unwrap());
-
-            if let Some(client) = prev_client {
-                ewmh_set_focus(
-                    &context.conn,
-                    context.atoms,
-                    client.window,
-                    EwmhFocusAction::Unfocus,
-                )
-                .ok();
-            }
-            if let Some(client) = curr_client {
-                ewmh_set_focus(
-                    &context.conn,
-                    context.atoms,
-                    client.window,
-                    EwmhFocusAction::Focus,
-                )
-                .ok();
-                ewmh_set_active_window(
-                    &context.conn,
-                    screen_manager.root(),
-                    context.atoms,
-                    client.window,
-                )
-                .ok();
-            }
+            Self::transfer_focus(context, &screen_manager, prev_client, curr_client);
         }
 
         drop(screen_manager);
@@ -129,6 +491,115 @@ impl LayoutManager {
         Ok(())
     }
 
+    /// Focuses one of the screens, either by its number or the one after the active screen
+    pub fn focus_screen(
+        &self,
+        context: &EventContext<xcb::x::KeyPressEvent>,
+        action: AvailableActions,
+    ) -> anyhow::Result<()> {
+        let mut screen_manager = context.screen_manager.borrow_mut();
+        let screen_idx = match action {
+            AvailableActions::FocusScreen1 => 0,
+            AvailableActions::FocusScreen2 => 1,
+            AvailableActions::FocusScreen3 => 2,
+            AvailableActions::FocusScreen4 => 3,
+            AvailableActions::FocusNextScreen => screen_manager.next_screen_idx(),
+            _ => unreachable!(),
+        };
+
+        if screen_idx.ge(&screen_manager.screens().len())
+            || screen_idx.eq(&screen_manager.active_screen_idx())
+        {
+            return Ok(());
+        }
+
+        let prev_client = screen_manager
+            .get_focused_client()
+            .map(|client| client.frame);
+        let curr_client = screen_manager.focus_screen(screen_idx);
+        Self::transfer_focus(context, &screen_manager, prev_client, curr_client);
+
+        drop(screen_manager);
+        self.display_screens(&context.screen_manager, context.decorator)?;
+
+        Ok(())
+    }
+
+    /// Moves the focused client to one of the screens, either by its number or the one after
+    /// the active screen, focus follows the client
+    pub fn move_to_screen(
+        &self,
+        context: &EventContext<xcb::x::KeyPressEvent>,
+        action: AvailableActions,
+    ) -> anyhow::Result<()> {
+        let mut screen_manager = context.screen_manager.borrow_mut();
+        let screen_idx = match action {
+            AvailableActions::MoveToScreen1 => 0,
+            AvailableActions::MoveToScreen2 => 1,
+            AvailableActions::MoveToScreen3 => 2,
+            AvailableActions::MoveToScreen4 => 3,
+            AvailableActions::MoveToNextScreen => screen_manager.next_screen_idx(),
+            _ => unreachable!(),
+        };
+
+        if screen_idx.ge(&screen_manager.screens().len())
+            || screen_idx.eq(&screen_manager.active_screen_idx())
+        {
+            return Ok(());
+        }
+
+        let Some(client) = screen_manager
+            .get_focused_client()
+            .map(|client| client.frame)
+        else {
+            return Ok(());
+        };
+        screen_manager.move_client_to_screen(client, screen_idx);
+        Self::transfer_focus(context, &screen_manager, None, Some(client));
+
+        drop(screen_manager);
+        self.display_screens(&context.screen_manager, context.decorator)?;
+
+        Ok(())
+    }
+
+    /// updates the focus hints after focus moved from `prev_client` to `curr_client`
+    fn transfer_focus(
+        context: &EventContext<xcb::x::KeyPressEvent>,
+        screen_manager: &ScreenManager,
+        prev_client: Option<xcb::x::Window>,
+        curr_client: Option<xcb::x::Window>,
+    ) {
+        let prev_client = prev_client.and_then(|client| screen_manager.clients().get(&client));
+        let curr_client = curr_client.and_then(|client| screen_manager.clients().get(&client));
+
+        if let Some(client) = prev_client {
+            ewmh_set_focus(
+                &context.conn,
+                context.atoms,
+                client.window,
+                EwmhFocusAction::Unfocus,
+            )
+            .ok();
+        }
+        if let Some(client) = curr_client {
+            ewmh_set_focus(
+                &context.conn,
+                context.atoms,
+                client.window,
+                EwmhFocusAction::Focus,
+            )
+            .ok();
+            ewmh_set_active_window(
+                &context.conn,
+                screen_manager.root(),
+                context.atoms,
+                client.window,
+            )
+            .ok();
+        }
+    }
+
     #[tracing::instrument(skip_all, err)]
     pub fn move_client(
         &self,
@@ -140,9 +611,8 @@ impl LayoutManager {
         let screen = screen_manager.screen(active_screen_idx);
         let workspace = screen.active_workspace();
 
-        let result = match workspace.layout() {
-            WorkspaceLayout::Tall => TallLayout::move_client(&mut screen_manager, direction),
-        };
+        let layout = workspace.layout().as_layout();
+        let result = layout.move_client(&mut screen_manager, direction);
 
         if let Some(focused_client) = result {
             let focused_client = screen_manager.clients().get(&focused_client).unwrap();
@@ -174,29 +644,57 @@ impl LayoutManager {
         action: AvailableActions,
     ) -> anyhow::Result<()> {
         let mut screen_manager = context.screen_manager.borrow_mut();
-        let index = screen_manager.active_screen_idx();
-        let screen = screen_manager.screen_mut(index);
-        let active_workspace_id = screen.active_workspace().id();
-
-        match action {
-            AvailableActions::Workspace1 => screen.set_active_workspace(0),
-            AvailableActions::Workspace2 => screen.set_active_workspace(1),
-            AvailableActions::Workspace3 => screen.set_active_workspace(2),
-            AvailableActions::Workspace4 => screen.set_active_workspace(3),
-            AvailableActions::Workspace5 => screen.set_active_workspace(4),
-            AvailableActions::Workspace6 => screen.set_active_workspace(5),
-            AvailableActions::Workspace7 => screen.set_active_workspace(6),
-            AvailableActions::Workspace8 => screen.set_active_workspace(7),
-            AvailableActions::Workspace9 => screen.set_active_workspace(8),
-            _ => {}
+        let workspace = match action {
+            AvailableActions::Workspace1 => 0,
+            AvailableActions::Workspace2 => 1,
+            AvailableActions::Workspace3 => 2,
+            AvailableActions::Workspace4 => 3,
+            AvailableActions::Workspace5 => 4,
+            AvailableActions::Workspace6 => 5,
+            AvailableActions::Workspace7 => 6,
+            AvailableActions::Workspace8 => 7,
+            AvailableActions::Workspace9 => 8,
+            _ => return Ok(()),
         };
 
-        if screen.active_workspace().id().ne(&active_workspace_id) {
-            self.hide_workspace(&screen.workspaces()[active_workspace_id as usize]);
-            drop(screen_manager);
-            self.display_screens(&context.screen_manager, context.decorator)?;
+        // switching workspaces might change what more than one screen displays when
+        // workspaces are shared between screens
+        let displayed_clients = |screen_manager: &ScreenManager| {
+            screen_manager
+                .screens()
+                .iter()
+                .flat_map(|screen| screen.active_workspace().clients().to_vec())
+                .collect::<Vec<_>>()
+        };
+        let active_screen = screen_manager.active_screen_idx();
+        let previously_displayed = displayed_clients(&screen_manager);
+        let previous_workspaces = screen_manager
+            .screens()
+            .iter()
+            .map(|screen| screen.active_workspace().id())
+            .collect::<Vec<_>>();
+
+        screen_manager.switch_workspace(workspace);
+
+        let workspaces = screen_manager
+            .screens()
+            .iter()
+            .map(|screen| screen.active_workspace().id())
+            .collect::<Vec<_>>();
+        if workspaces.eq(&previous_workspaces)
+            && screen_manager.active_screen_idx().eq(&active_screen)
+        {
+            return Ok(());
         }
 
+        let displayed = displayed_clients(&screen_manager);
+        previously_displayed
+            .iter()
+            .filter(|client| !displayed.contains(client))
+            .for_each(|client| self.hide_client(client));
+        drop(screen_manager);
+        self.display_screens(&context.screen_manager, context.decorator)?;
+
         Ok(())
     }
 
@@ -211,8 +709,7 @@ impl LayoutManager {
             let client_frame = active_client.frame;
             let screen = screen_manager.screen_mut(index);
             let active_workspace_id = screen.active_workspace_id();
-            let workspaces = screen.workspaces_mut();
-            workspaces[active_workspace_id].remove_client(client_frame);
+            screen.workspaces_mut()[active_workspace_id].remove_client(client_frame);
 
             let new_workspace_id = match action {
                 AvailableActions::MoveToWorkspace1 => 0,
@@ -227,9 +724,14 @@ impl LayoutManager {
                 _ => unreachable!(),
             };
 
-            workspaces[new_workspace_id]
+            // shared workspaces might be held by another screen
+            let target_screen = screen_manager.workspace_screen_idx(index, new_workspace_id as u8);
+            screen_manager.screen_mut(target_screen).workspaces_mut()[new_workspace_id]
                 .clients_mut()
                 .push(client_frame);
+            if let Some(client) = screen_manager.clients_mut().get_mut(&client_frame) {
+                client.workspace = new_workspace_id as u8;
+            }
 
             self.hide_client(&client_frame);
         }
@@ -240,12 +742,23 @@ impl LayoutManager {
         Ok(())
     }
 
-    fn hide_workspace(&self, workspace: &Workspace) {
+    pub fn hide_workspace(&self, workspace: &Workspace) {
         for client in workspace.clients() {
             self.hide_client(client);
         }
     }
 
+    /// hides every client that isn't on the active workspace of its screen
+    pub fn hide_inactive_workspaces(&self, screen_manager: &ScreenManager) {
+        for screen in screen_manager.screens() {
+            screen
+                .workspaces()
+                .iter()
+                .filter(|workspace| workspace.id().ne(&screen.active_workspace().id()))
+                .for_each(|workspace| self.hide_workspace(workspace));
+        }
+    }
+
     fn hide_client(&self, client: &xcb::x::Window) {
         xcb_unmap_win!(self.conn, *client);
     }
@@ -265,8 +778,7 @@ impl LayoutManager {
                 .map(|cookie| {
                     cookie
                         .value::<xcb::x::Atom>()
-                        .iter()
-                        .any(|&atom| atom == atoms.wm_delete_window)
+                        .contains(&atoms.wm_delete_window)
                 })
                 .unwrap_or(false);
 
@@ -298,3 +810,22 @@ impl LayoutManager {
         Ok(())
     }
 }
+
+pub fn configure_window(conn: &Arc<xcb::Connection>, window: xcb::x::Window, position: Position) {
+    conn.send_request(&xcb::x::ConfigureWindow {
+        window,
+        value_list: &[
+            xcb::x::ConfigWindow::X(position.x),
+            xcb::x::ConfigWindow::Y(position.y),
+            xcb::x::ConfigWindow::Width(position.width),
+            xcb::x::ConfigWindow::Height(position.height),
+        ],
+    });
+}
+
+/// floating clients that never had a geometry of their own are centered on the screen, using
+/// half of its available area
+fn default_floating_position(screen: &Screen) -> Position {
+    let area = screen.get_available_area();
+    area.centered(area.width / 2, area.height / 2)
+}
@@ -0,0 +1,34 @@
+use config::Config;
+
+use crate::layout_manager::layout::{Layout, Placement};
+use crate::layout_manager::tall_layout::TallLayout;
+use crate::screen::{Client, Screen};
+use crate::screen_manager::{Direction, ScreenManager};
+
+/// Every client on a floating workspace is floating, and is displayed with its own geometry
+/// by the `LayoutManager`, so there is nothing to arrange.
+///
+/// Moving focus and clients around follows the order of the tall layout.
+pub struct FloatingLayout {}
+
+impl Layout for FloatingLayout {
+    fn arrange(&self, _: &Screen, _: &[&Client], _: &Config) -> Vec<Placement> {
+        vec![]
+    }
+
+    fn focus_client(
+        &self,
+        screen_manager: &mut ScreenManager,
+        direction: Direction,
+    ) -> anyhow::Result<Option<(Option<xcb::x::Window>, Option<xcb::x::Window>)>> {
+        TallLayout {}.focus_client(screen_manager, direction)
+    }
+
+    fn move_client(
+        &self,
+        screen_manager: &mut ScreenManager,
+        direction: Direction,
+    ) -> Option<xcb::x::Window> {
+        TallLayout {}.move_client(screen_manager, direction)
+    }
+}
@@ -0,0 +1,157 @@
+use config::Config;
+
+use crate::layout_manager::default_floating_position;
+use crate::layout_manager::floating_layout::FloatingLayout;
+use crate::layout_manager::monocle_layout::MonocleLayout;
+use crate::layout_manager::tall_layout::TallLayout;
+use crate::position::Position;
+use crate::screen::{Client, Screen, WorkspaceLayout};
+use crate::screen_manager::{Direction, ScreenManager};
+
+/// Where a tiled client should be displayed, as computed by a `Layout`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Placement {
+    /// position of the frame, relative to the root window
+    pub frame: Position,
+    /// position of the client window, relative to its frame
+    pub window: Position,
+    /// whether the frame should be stacked above its siblings
+    pub raise: bool,
+}
+
+impl Placement {
+    pub fn new(frame: Position, window: Position) -> Self {
+        Placement {
+            frame,
+            window,
+            raise: false,
+        }
+    }
+}
+
+/// A way of arranging the tiled clients of a workspace.
+///
+/// Layouts only compute where clients should go and how focus moves between them, it is up to
+/// the `LayoutManager` to issue the requests that actually display the clients.
+pub trait Layout {
+    /// computes the placement of every tiled client of the active workspace of `screen`,
+    /// returning one placement per client, in the same order as `clients`
+    fn arrange(&self, screen: &Screen, clients: &[&Client], config: &Config) -> Vec<Placement>;
+
+    /// focus a client in a given direction. Possibly focusing a client on an adjacent
+    /// screen, this function will never fail, but might return a few set of different
+    /// data, explained below:
+    ///
+    /// - `None` -> `Screen` has no clients that can be focused
+    /// - `Some((None, Some(_)))` -> `Screen` has no focused client, and we focused one
+    /// - `Some((Some(_), Some(_)))` -> `Screen` had a focused client, and we changed focus
+    ///
+    /// The order is always `Some((Some(old_client), Some(new_client)))`
+    fn focus_client(
+        &self,
+        screen_manager: &mut ScreenManager,
+        direction: Direction,
+    ) -> anyhow::Result<Option<(Option<xcb::x::Window>, Option<xcb::x::Window>)>>;
+
+    /// moves the focused client in a given direction, possibly to an adjacent screen.
+    /// Returns the client that got focused in the process, if any
+    fn move_client(
+        &self,
+        screen_manager: &mut ScreenManager,
+        direction: Direction,
+    ) -> Option<xcb::x::Window>;
+}
+
+/// Focuses the client on the active workspace of `screen_idx` displayed nearest to `client`,
+/// making it the active screen. This is how focus continues onto an adjacent screen, when
+/// `client` is `None` the distance is measured from the active screen instead.
+///
+/// Returns the newly focused client, if the screen has any
+pub fn focus_nearest_client(
+    screen_manager: &mut ScreenManager,
+    screen_idx: usize,
+    client: Option<xcb::x::Window>,
+) -> Option<xcb::x::Window> {
+    let active_screen = screen_manager.screen(screen_manager.active_screen_idx());
+    let origin = client
+        .and_then(|client| {
+            client_positions(screen_manager, active_screen)
+                .into_iter()
+                .find(|(frame, _)| frame.eq(&client))
+        })
+        .map(|(_, position)| position)
+        .unwrap_or_else(|| active_screen.position().clone())
+        .center();
+
+    // clients sharing the same place, as on monocle workspaces, favor the focused one
+    let screen = screen_manager.screen(screen_idx);
+    let nearest_client = client_positions(screen_manager, screen)
+        .into_iter()
+        .min_by_key(|(frame, position)| {
+            let (x, y) = position.center();
+            let distance = ((x - origin.0) as i64).pow(2) + ((y - origin.1) as i64).pow(2);
+            (distance, screen.focused_client().ne(&Some(*frame)))
+        })
+        .map(|(frame, _)| frame);
+
+    screen_manager.set_active_screen(screen_idx);
+    if nearest_client.is_some() {
+        screen_manager
+            .screen_mut(screen_idx)
+            .active_workspace_mut()
+            .set_focused_client(nearest_client);
+    }
+
+    nearest_client
+}
+
+/// where every visible client on the active workspace of `screen` is displayed, placing them
+/// just like the `LayoutManager` does
+fn client_positions(
+    screen_manager: &ScreenManager,
+    screen: &Screen,
+) -> Vec<(xcb::x::Window, Position)> {
+    let (fullscreen_clients, clients): (Vec<_>, Vec<_>) = screen_manager
+        .get_visible_screen_clients(screen)
+        .into_iter()
+        .filter(|client| client.visible)
+        .partition(|client| client.fullscreen);
+    let (floating_clients, tiled_clients): (Vec<_>, Vec<_>) = clients
+        .into_iter()
+        .partition(|client| screen_manager.is_floating(client.frame));
+
+    let placements = screen.active_workspace().layout().as_layout().arrange(
+        screen,
+        &tiled_clients,
+        &screen_manager.config().borrow(),
+    );
+
+    tiled_clients
+        .iter()
+        .zip(placements)
+        .map(|(client, placement)| (client.frame, placement.frame))
+        .chain(floating_clients.iter().map(|client| {
+            let position = client
+                .floating_position
+                .clone()
+                .unwrap_or_else(|| default_floating_position(screen));
+            (client.frame, position)
+        }))
+        .chain(
+            fullscreen_clients
+                .iter()
+                .map(|client| (client.frame, screen.position().clone())),
+        )
+        .collect()
+}
+
+impl WorkspaceLayout {
+    /// the layout implementation used to arrange workspaces using this layout
+    pub fn as_layout(&self) -> &'static dyn Layout {
+        match self {
+            WorkspaceLayout::Tall => &TallLayout {},
+            WorkspaceLayout::Monocle => &MonocleLayout {},
+            WorkspaceLayout::Floating => &FloatingLayout {},
+        }
+    }
+}
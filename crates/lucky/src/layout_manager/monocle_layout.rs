@@ -0,0 +1,218 @@
+use config::Config;
+
+use crate::layout_manager::layout::{focus_nearest_client, Layout, Placement};
+use crate::position::Position;
+use crate::screen::{Client, Screen};
+use crate::screen_manager::{Direction, ScreenManager};
+
+use std::ops::{Add, Mul, Sub};
+
+pub struct MonocleLayout {}
+
+impl MonocleLayout {
+    /// index of the client to the given direction, wrapping around the workspace
+    fn relative_index(screen: &Screen, client: xcb::x::Window, direction: Direction) -> usize {
+        let clients = screen.active_workspace().clients();
+        let index = clients
+            .iter()
+            .position(|c| c.eq(&client))
+            .expect("workspace clients vector should include selected client");
+
+        match direction {
+            Direction::Up | Direction::Left => index.add(clients.len()).sub(1) % clients.len(),
+            Direction::Down | Direction::Right => index.add(1) % clients.len(),
+        }
+    }
+
+    fn focus_first(screen: &mut Screen) -> Option<xcb::x::Window> {
+        let first_client = screen.active_workspace().clients().first().copied();
+
+        screen
+            .active_workspace_mut()
+            .set_focused_client(first_client);
+
+        first_client
+    }
+}
+
+impl Layout for MonocleLayout {
+    /// every client fills the available area, and the focused client of the workspace is
+    /// stacked above the others so it is the only one visible
+    fn arrange(&self, screen: &Screen, clients: &[&Client], config: &Config) -> Vec<Placement> {
+        let available_area = screen.get_tiled_area(clients.len());
+
+        let top_client = clients
+            .iter()
+            .position(|client| screen.focused_client().is_some_and(|c| c.eq(&client.frame)))
+            .unwrap_or_default();
+
+        clients
+            .iter()
+            .enumerate()
+            .map(|(i, client)| {
+                let border_double = client.border_width(config).mul(2) as u32;
+                let width = available_area.width.saturating_sub(border_double);
+                let height = available_area.height.saturating_sub(border_double);
+
+                Placement {
+                    frame: Position::new(available_area.x, available_area.y, width, height),
+                    window: Position::new(0, 0, width, height),
+                    raise: i.eq(&top_client),
+                }
+            })
+            .collect()
+    }
+
+    /// cycles the focus through the clients of the workspace with `Up` and `Down`, while
+    /// `Left` and `Right` focus the adjacent screens
+    fn focus_client(
+        &self,
+        screen_manager: &mut ScreenManager,
+        direction: Direction,
+    ) -> anyhow::Result<Option<(Option<xcb::x::Window>, Option<xcb::x::Window>)>> {
+        let index = screen_manager.active_screen_idx();
+        let screen = screen_manager.screen_mut(index);
+
+        if screen.active_workspace().clients().is_empty() {
+            let Some(new_screen) = screen_manager.get_relative_screen_idx(direction) else {
+                return Ok(None);
+            };
+
+            let focused_client = focus_nearest_client(screen_manager, new_screen, None);
+            return Ok(Some((None, focused_client)));
+        }
+
+        let Some(client) = screen.focused_client() else {
+            return Ok(Some((None, Self::focus_first(screen))));
+        };
+
+        match direction {
+            Direction::Up | Direction::Down => {
+                let next = Self::relative_index(screen, client, direction);
+                let focused_client = screen.active_workspace().clients().get(next).copied();
+                screen
+                    .active_workspace_mut()
+                    .set_focused_client(focused_client);
+
+                Ok(Some((Some(client), focused_client)))
+            }
+            Direction::Left | Direction::Right => {
+                let Some(new_screen) = screen_manager.get_relative_screen_idx(direction) else {
+                    return Ok(None);
+                };
+
+                let focused_client = focus_nearest_client(screen_manager, new_screen, Some(client));
+                Ok(Some((Some(client), focused_client)))
+            }
+        }
+    }
+
+    /// swaps the focused client with the previous or next one with `Up` and `Down`, while
+    /// `Left` and `Right` move it to the adjacent screens
+    fn move_client(
+        &self,
+        screen_manager: &mut ScreenManager,
+        direction: Direction,
+    ) -> Option<xcb::x::Window> {
+        let index = screen_manager.active_screen_idx();
+        let screen = screen_manager.screen_mut(index);
+
+        if screen.active_workspace().clients().is_empty() {
+            return None;
+        }
+
+        let Some(client) = screen.focused_client() else {
+            return Self::focus_first(screen);
+        };
+
+        match direction {
+            Direction::Up | Direction::Down => {
+                let current = screen
+                    .active_workspace()
+                    .clients()
+                    .iter()
+                    .position(|c| c.eq(&client))?;
+                let next = Self::relative_index(screen, client, direction);
+                screen
+                    .active_workspace_mut()
+                    .clients_mut()
+                    .swap(current, next);
+            }
+            Direction::Left | Direction::Right => {
+                let new_screen = screen_manager.get_relative_screen_idx(direction)?;
+                screen_manager.move_client_to_screen(client, new_screen);
+
+                return Some(client);
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use xcb::XidNew;
+
+    #[test]
+    fn test_focus_cycles_through_clients() {
+        let config = Rc::new(RefCell::new(Config::default()));
+        let root = unsafe { xcb::x::Window::new(0) };
+        let screen_positions = vec![Screen::new(&config, "", Position::new(0, 0, 100, 100))];
+        let mut screen_manager = ScreenManager::new(screen_positions, config, root);
+
+        let clients = (1..=3)
+            .map(|id| unsafe { (xcb::x::Window::new(id), xcb::x::Window::new(id + 10)) })
+            .collect::<Vec<_>>();
+        for (frame, window) in clients.iter() {
+            screen_manager.create_client(*frame, *window, None, None);
+        }
+        let (first, _) = clients[0];
+        let (last, _) = clients[2];
+        let workspace = screen_manager.screen_mut(0).active_workspace_mut();
+        workspace.set_focused_client(Some(first));
+
+        // going up from the first client wraps around to the last one
+        MonocleLayout {}
+            .focus_client(&mut screen_manager, Direction::Up)
+            .unwrap();
+        assert!(screen_manager.screen(0).focused_client().eq(&Some(last)));
+
+        // and going down from the last one goes back to the first
+        MonocleLayout {}
+            .focus_client(&mut screen_manager, Direction::Down)
+            .unwrap();
+        assert!(screen_manager.screen(0).focused_client().eq(&Some(first)));
+    }
+
+    #[test]
+    fn test_clients_without_borders_fill_the_screen() {
+        let config = Rc::new(RefCell::new(Config::default()));
+        let root = unsafe { xcb::x::Window::new(0) };
+        let screen_positions = vec![Screen::new(&config, "", Position::new(0, 0, 100, 100))];
+        let mut screen_manager = ScreenManager::new(screen_positions, config.clone(), root);
+
+        let (frame_a, frame_b) = unsafe { (xcb::x::Window::new(1), xcb::x::Window::new(2)) };
+        screen_manager.create_client(frame_a, unsafe { xcb::x::Window::new(11) }, None, None);
+        screen_manager.create_client(frame_b, unsafe { xcb::x::Window::new(12) }, None, None);
+        screen_manager
+            .clients_mut()
+            .get_mut(&frame_b)
+            .unwrap()
+            .no_border = true;
+
+        let screen = screen_manager.screen(0);
+        let clients = [
+            &screen_manager.clients()[&frame_a],
+            &screen_manager.clients()[&frame_b],
+        ];
+        let placements = MonocleLayout {}.arrange(screen, &clients, &config.borrow());
+
+        let border_double = config.borrow().border_width() as u32 * 2;
+        assert!(placements[0].frame.width.eq(&(100 - border_double)));
+        assert!(placements[1].frame.width.eq(&100));
+    }
+}
@@ -1,140 +1,83 @@
 use anyhow::Context;
 use config::Config;
 
-use crate::decorator::Decorator;
+use crate::layout_manager::layout::{focus_nearest_client, Layout, Placement};
 use crate::position::Position;
 use crate::screen::{Client, Screen};
 use crate::screen_manager::{Direction, ScreenManager};
-use crate::xcb_utils::xcb_map_win;
 
-use std::cell::RefCell;
-use std::ops::{Add, Div, Mul, Sub};
-use std::rc::Rc;
-use std::sync::Arc;
+use std::ops::{Add, Mul, Sub};
 
 pub struct TallLayout {}
 
 impl TallLayout {
-    pub fn display_clients(
-        conn: &Arc<xcb::Connection>,
-        config: &Rc<RefCell<Config>>,
-        screen: &Screen,
-        clients: Vec<&Client>,
-        focused_client: Option<&Client>,
-        decorator: &Decorator,
-    ) -> anyhow::Result<()> {
-        let visible_clients_len = clients.len();
-        let available_area = screen.get_available_area();
-
-        let main_width = if visible_clients_len.eq(&1) {
-            available_area.width
-        } else {
-            available_area.width.div(2)
-        };
-
-        for client in screen.reserved_clients() {
-            Self::configure_window(conn, client.window, client.position.clone());
-            conn.send_request(&xcb::x::MapWindow {
-                window: client.window,
-            });
-        }
-
-        for (i, client) in clients.iter().enumerate() {
-            decorator
-                .unfocus_client(client)
-                .context("failed to unfocus client")?;
-            match i {
-                0 => Self::display_main_client(conn, client, &available_area, main_width, config),
-                _ => Self::display_side_client(
-                    conn,
-                    client,
-                    &available_area,
-                    i,
-                    visible_clients_len,
-                    main_width,
-                    config,
-                ),
-            }
-        }
-
-        let Some(focused_client) = focused_client else {
-            return Ok(());
-        };
-
-        clients
-            .iter()
-            .find(|&&client| client == focused_client)
-            .map(|client| decorator.focus_client(client));
-
-        Ok(())
+    /// splits the height of a column between `total` clients separated by `gap`, returning the
+    /// offset and height of the client at `index`
+    fn split_column(column: &Position, index: usize, total: usize, gap: u32) -> (i32, u32) {
+        let gaps = gap.mul(total.sub(1) as u32);
+        let height = column.height.saturating_sub(gaps).div_ceil(total as u32);
+        let position_y = height.add(gap).mul(index as u32) as i32;
+        (position_y, height)
     }
 
-    fn display_main_client(
-        conn: &Arc<xcb::Connection>,
-        client: &Client,
-        available_area: &Position,
-        main_width: u32,
-        config: &Rc<RefCell<Config>>,
-    ) {
-        let border_double = config.borrow().border_width().mul(2) as u32;
-        let frame_position = Position::new(
-            available_area.x,
-            available_area.y,
-            main_width.sub(border_double),
-            available_area.height.sub(border_double),
-        );
-        let client_position = Position::new(
-            0,
-            0,
-            main_width.sub(config.borrow().border_width() as u32),
-            available_area
-                .height
-                .sub(config.borrow().border_width() as u32),
-        );
-
-        Self::configure_window(conn, client.frame, frame_position);
-        Self::configure_window(conn, client.window, client_position);
+    /// places the client at `index` out of the `total` clients sharing the master column
+    fn main_placement(
+        column: &Position,
+        index: usize,
+        total: usize,
+        gap: u32,
+        border_width: u32,
+    ) -> Placement {
+        let border_double = border_width.mul(2);
+        let (position_y, height) = Self::split_column(column, index, total, gap);
 
-        xcb_map_win!(conn, client.window);
-        xcb_map_win!(conn, client.frame);
+        Placement::new(
+            Position::new(
+                column.x,
+                column.y.add(position_y),
+                column.width.saturating_sub(border_double),
+                height.saturating_sub(border_double),
+            ),
+            Position::new(
+                0,
+                0,
+                column.width.saturating_sub(border_width),
+                height.saturating_sub(border_width),
+            ),
+        )
     }
 
-    fn display_side_client(
-        conn: &Arc<xcb::Connection>,
-        client: &Client,
-        available_area: &Position,
+    /// places the client at `index` out of the `total` clients stacked on the side column
+    fn side_placement(
+        column: &Position,
         index: usize,
         total: usize,
-        main_width: u32,
-        config: &Rc<RefCell<Config>>,
-    ) {
-        let width = available_area.width.sub(main_width);
-        let total_siblings = total.sub(1);
-        let height = available_area.height.div_ceil(total_siblings as u32);
-        let sibling_index = index.sub(1);
-        let border_double = config.borrow().border_width().mul(2) as u32;
-        let position_y = height.mul(sibling_index as u32) as i32;
-
-        let height = height.sub(border_double);
-
-        Self::configure_window(
-            conn,
-            client.frame,
-            Position::new(
-                available_area.x.add(main_width as i32),
-                available_area.y.add(position_y),
-                width.sub(border_double),
-                height,
-            ),
-        );
-        Self::configure_window(
-            conn,
-            client.window,
-            Position::new(0, 0, width.sub(border_double), height),
-        );
+        gap: u32,
+        border_width: u32,
+    ) -> Placement {
+        let border_double = border_width.mul(2);
+        let (position_y, height) = Self::split_column(column, index, total, gap);
+        let width = column.width.saturating_sub(border_double);
+        let height = height.saturating_sub(border_double);
+
+        Placement::new(
+            Position::new(column.x, column.y.add(position_y), width, height),
+            Position::new(0, 0, width, height),
+        )
+    }
+
+    /// whether the client lives on the master column, which is made of the first
+    /// `master_count` of the `tiled` clients of the workspace
+    fn is_master(screen: &Screen, tiled: &[xcb::x::Window], client: xcb::x::Window) -> bool {
+        tiled
+            .iter()
+            .position(|c| c.eq(&client))
+            .is_some_and(|index| index.lt(&screen.active_workspace().master_count()))
+    }
 
-        xcb_map_win!(conn, client.window);
-        xcb_map_win!(conn, client.frame);
+    /// whether there are `tiled` clients on the side column of the active workspace
+    fn has_side_clients(screen: &Screen, tiled: &[xcb::x::Window]) -> bool {
+        tiled.len().gt(&screen.active_workspace().master_count())
     }
 
     fn is_first(screen: &mut Screen, client: xcb::x::Window) -> bool {
@@ -165,6 +108,34 @@ impl TallLayout {
         Ok(())
     }
 
+    /// swaps a client on the master column with the first of the `tiled` clients on the side
+    /// column
+    fn swap_first_side(
+        screen: &mut Screen,
+        tiled: &[xcb::x::Window],
+        client: xcb::x::Window,
+    ) -> anyhow::Result<()> {
+        let position = |window: &xcb::x::Window| {
+            screen
+                .active_workspace()
+                .clients()
+                .iter()
+                .position(|c| c.eq(window))
+                .context("workspace clients vector should include selected client")
+        };
+        let index = position(&client)?;
+        let first_side = tiled
+            .get(screen.active_workspace().master_count())
+            .context("side column should have clients")?;
+        let first_side = position(first_side)?;
+
+        screen
+            .active_workspace_mut()
+            .clients_mut()
+            .swap(index, first_side);
+        Ok(())
+    }
+
     fn swap_prev(screen: &mut Screen, client: xcb::x::Window) -> anyhow::Result<()> {
         let index = screen
             .active_workspace()
@@ -207,6 +178,15 @@ impl TallLayout {
         first_client
     }
 
+    /// focus the first of the `tiled` clients on the side column
+    fn focus_first_side(screen: &mut Screen, tiled: &[xcb::x::Window]) -> Option<xcb::x::Window> {
+        let first_side = tiled.get(screen.active_workspace().master_count()).copied();
+
+        screen.active_workspace_mut().set_focused_client(first_side);
+
+        first_side
+    }
+
     fn focus_last(screen: &mut Screen) -> Option<xcb::x::Window> {
         let last_client = screen.active_workspace().clients().last().copied();
         screen
@@ -253,27 +233,81 @@ impl TallLayout {
 
         client
     }
+}
 
-    /// focus a client in a given direction. Possibly focusing a client on an adjacent
-    /// screen, this function will never fail, but might return a few set of different
-    /// data, explained below:
-    ///
-    /// - `None` -> `Screen` has no clients that can be focused
-    /// - `Some((None, Some(_)))` -> `Screen` has no focused client, and we focused one
-    /// - `Some((Some(_), Some(_)))` -> `Screen` had a focused client, and we changed focus
-    ///
-    /// The order is always `Some((Some(old_client), Some(new_client)))`
-    ///
-    /// TODO: when there are no clients on the current screen, try focusing on the adjacent
-    pub fn focus_client(
+impl Layout for TallLayout {
+    fn arrange(&self, screen: &Screen, clients: &[&Client], config: &Config) -> Vec<Placement> {
+        let visible_clients_len = clients.len();
+        let available_area = screen.get_tiled_area(visible_clients_len);
+        let master_count = screen
+            .active_workspace()
+            .master_count()
+            .min(visible_clients_len);
+        let side_count = visible_clients_len.sub(master_count);
+
+        let gap = screen.active_workspace().inner_gap(visible_clients_len);
+        let main_width = match (master_count, side_count) {
+            (_, 0) => available_area.width,
+            (0, _) => 0,
+            _ => ((available_area.width as f32).mul(screen.active_workspace().master_ratio())
+                as u32)
+                .saturating_sub(gap.div_ceil(2)),
+        };
+        let side_offset = match (master_count, side_count) {
+            (0, _) | (_, 0) => main_width,
+            _ => main_width.add(gap),
+        };
+
+        let main_column = Position::new(
+            available_area.x,
+            available_area.y,
+            main_width,
+            available_area.height,
+        );
+        let side_column = Position::new(
+            available_area.x.add(side_offset as i32),
+            available_area.y,
+            available_area.width.saturating_sub(side_offset),
+            available_area.height,
+        );
+
+        // clients without borders fill the whole space they are given
+        clients
+            .iter()
+            .enumerate()
+            .map(|(i, client)| (i, client.border_width(config) as u32))
+            .map(|(i, border_width)| match i {
+                _ if i.lt(&master_count) => {
+                    Self::main_placement(&main_column, i, master_count, gap, border_width)
+                }
+                _ => Self::side_placement(
+                    &side_column,
+                    i.sub(master_count),
+                    side_count,
+                    gap,
+                    border_width,
+                ),
+            })
+            .collect()
+    }
+
+    fn focus_client(
+        &self,
         screen_manager: &mut ScreenManager,
         direction: Direction,
     ) -> anyhow::Result<Option<(Option<xcb::x::Window>, Option<xcb::x::Window>)>> {
         let index = screen_manager.active_screen_idx();
+        let tiled = screen_manager.tiled_clients(screen_manager.screen(index));
         let screen = screen_manager.screen_mut(index);
 
+        // an empty workspace has no edges to reach, so focus goes straight to the adjacent screen
         if screen.active_workspace().clients().is_empty() {
-            return Ok(None);
+            let Some(new_screen) = screen_manager.get_relative_screen_idx(direction) else {
+                return Ok(None);
+            };
+
+            let focused_client = focus_nearest_client(screen_manager, new_screen, None);
+            return Ok(Some((None, focused_client)));
         }
 
         let Some(client) = screen.focused_client() else {
@@ -288,10 +322,15 @@ impl TallLayout {
         };
 
         let should_change_screen = match direction {
-            Direction::Left => Self::is_first(screen, client),
+            Direction::Left => {
+                Self::is_master(screen, &tiled, client)
+                    || screen.active_workspace().master_count().eq(&0)
+            }
             Direction::Down => Self::is_last(screen, client),
             Direction::Up => Self::is_first(screen, client),
-            Direction::Right => Self::is_last(screen, client),
+            Direction::Right => {
+                !Self::has_side_clients(screen, &tiled) || !Self::is_master(screen, &tiled, client)
+            }
         };
 
         if should_change_screen {
@@ -299,16 +338,7 @@ impl TallLayout {
                 return Ok(None);
             };
 
-            screen_manager.set_active_screen(new_screen);
-            let screen = screen_manager.screen_mut(new_screen);
-
-            let focused_client = match direction {
-                Direction::Left => Self::focus_last(screen),
-                Direction::Down => Self::focus_first(screen),
-                Direction::Up => Self::focus_last(screen),
-                Direction::Right => Self::focus_first(screen),
-            };
-
+            let focused_client = focus_nearest_client(screen_manager, new_screen, Some(client));
             return Ok(Some((Some(client), focused_client)));
         }
 
@@ -316,17 +346,19 @@ impl TallLayout {
             Direction::Left => Self::focus_first(screen),
             Direction::Down => Self::focus_next(screen, client),
             Direction::Up => Self::focus_prev(screen, client),
-            Direction::Right => Self::focus_next(screen, client),
+            Direction::Right => Self::focus_first_side(screen, &tiled),
         };
 
         Ok(Some((Some(client), focused_client)))
     }
 
-    pub fn move_client(
+    fn move_client(
+        &self,
         screen_manager: &mut ScreenManager,
         direction: Direction,
     ) -> Option<xcb::x::Window> {
         let index = screen_manager.active_screen_idx();
+        let tiled = screen_manager.tiled_clients(screen_manager.screen(index));
         let screen = screen_manager.screen_mut(index);
 
         if screen.active_workspace().clients().is_empty() {
@@ -344,59 +376,42 @@ impl TallLayout {
         };
 
         let should_change_screen = match direction {
-            Direction::Left => Self::is_first(screen, client),
+            Direction::Left => {
+                Self::is_master(screen, &tiled, client)
+                    || screen.active_workspace().master_count().eq(&0)
+            }
             Direction::Down => Self::is_last(screen, client),
             Direction::Up => Self::is_first(screen, client),
-            Direction::Right => Self::is_last(screen, client),
+            Direction::Right => {
+                !Self::has_side_clients(screen, &tiled) || !Self::is_master(screen, &tiled, client)
+            }
         };
 
         if should_change_screen {
-            let Some(new_screen) = screen_manager.get_relative_screen_idx(direction) else {
-                return None;
-            };
-
-            screen_manager
-                .screen_mut(index)
-                .active_workspace_mut()
-                .remove_client(client);
+            let new_screen = screen_manager.get_relative_screen_idx(direction)?;
+            screen_manager.move_client_to_screen(client, new_screen);
 
-            screen_manager
-                .screen_mut(new_screen)
-                .active_workspace_mut()
-                .new_client(client);
-
-            screen_manager.set_active_screen(new_screen);
-
-            return None;
+            return Some(client);
         }
 
         match direction {
             Direction::Left => Self::swap_first(screen, client),
             Direction::Down => Self::swap_next(screen, client),
             Direction::Up => Self::swap_prev(screen, client),
-            Direction::Right => Self::swap_next(screen, client),
-        };
+            Direction::Right => Self::swap_first_side(screen, &tiled, client),
+        }
+        .ok();
 
         None
     }
-
-    fn configure_window(conn: &Arc<xcb::Connection>, window: xcb::x::Window, client_pos: Position) {
-        conn.send_request(&xcb::x::ConfigureWindow {
-            window,
-            value_list: &[
-                xcb::x::ConfigWindow::X(client_pos.x),
-                xcb::x::ConfigWindow::Y(client_pos.y),
-                xcb::x::ConfigWindow::Width(client_pos.width),
-                xcb::x::ConfigWindow::Height(client_pos.height),
-            ],
-        });
-    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use rand::RngCore;
+    use std::cell::RefCell;
+    use std::rc::Rc;
     use xcb::XidNew;
 
     fn create_fake_client() -> (xcb::x::Window, xcb::x::Window) {
@@ -413,13 +428,13 @@ mod tests {
     fn test_client_focusing() {
         let config = Rc::new(RefCell::new(Config::default()));
         let root = unsafe { xcb::x::Window::new(0) };
-        let screen_positions = vec![Screen::new(&config, Position::new(0, 0, 100, 100))];
+        let screen_positions = vec![Screen::new(&config, "", Position::new(0, 0, 100, 100))];
         let mut screen_manager = ScreenManager::new(screen_positions, config, root);
 
         let (frame_a, client_a) = create_fake_client();
         let (frame_b, client_b) = create_fake_client();
-        screen_manager.create_client(frame_a, client_a);
-        screen_manager.create_client(frame_b, client_b);
+        screen_manager.create_client(frame_a, client_a, None, None);
+        screen_manager.create_client(frame_b, client_b, None, None);
         let screen = screen_manager.screen_mut(0);
         let workspace = screen.active_workspace_mut();
 
@@ -435,7 +450,9 @@ mod tests {
         // │          ││ selected │
         // └──────────┘└──────────┘
         // select the second one
-        TallLayout::focus_client(&mut screen_manager, Direction::Right).unwrap();
+        TallLayout {}
+            .focus_client(&mut screen_manager, Direction::Right)
+            .unwrap();
         let screen = screen_manager.screen_mut(0);
         assert!(screen.focused_client().eq(&Some(frame_b)));
 
@@ -443,7 +460,9 @@ mod tests {
         // │          ││ selected │
         // └──────────┘└──────────┘
         // since we are at the last, it should do nothing and return Unhandled
-        TallLayout::focus_client(&mut screen_manager, Direction::Right).unwrap();
+        TallLayout {}
+            .focus_client(&mut screen_manager, Direction::Right)
+            .unwrap();
         let screen = screen_manager.screen_mut(0);
         assert!(screen.focused_client().eq(&Some(frame_b)));
 
@@ -451,7 +470,9 @@ mod tests {
         // │ selected ││          │
         // └──────────┘└──────────┘
         // set the first one to be selected
-        TallLayout::focus_client(&mut screen_manager, Direction::Left).unwrap();
+        TallLayout {}
+            .focus_client(&mut screen_manager, Direction::Left)
+            .unwrap();
         let screen = screen_manager.screen_mut(0);
         assert!(screen.focused_client().eq(&Some(frame_a)));
 
@@ -459,8 +480,219 @@ mod tests {
         // │ selected ││          │
         // └──────────┘└──────────┘
         // similarly, when at the first, should do nothing and return unhandled
-        TallLayout::focus_client(&mut screen_manager, Direction::Left).unwrap();
+        TallLayout {}
+            .focus_client(&mut screen_manager, Direction::Left)
+            .unwrap();
+        let screen = screen_manager.screen_mut(0);
+        assert!(screen.focused_client().eq(&Some(frame_a)));
+    }
+
+    #[test]
+    fn test_focusing_with_multiple_masters() {
+        let config = Rc::new(RefCell::new(Config::default()));
+        let root = unsafe { xcb::x::Window::new(0) };
+        let screen_positions = vec![Screen::new(&config, "", Position::new(0, 0, 100, 100))];
+        let mut screen_manager = ScreenManager::new(screen_positions, config, root);
+
+        let (frame_a, client_a) = create_fake_client();
+        let (frame_b, client_b) = create_fake_client();
+        let (frame_c, client_c) = create_fake_client();
+        screen_manager.create_client(frame_a, client_a, None, None);
+        screen_manager.create_client(frame_b, client_b, None, None);
+        screen_manager.create_client(frame_c, client_c, None, None);
+        let workspace = screen_manager.screen_mut(0).active_workspace_mut();
+        workspace.change_master_count(1);
+        assert!(workspace.master_count().eq(&2));
+
+        // ┌──────────┐┌──────────┐
+        // │          ││          │
+        // ├──────────┤│          │
+        // │ selected ││          │
+        // └──────────┘└──────────┘
+        // the second master should move to the side column
+        workspace.set_focused_client(Some(frame_b));
+        TallLayout {}
+            .focus_client(&mut screen_manager, Direction::Right)
+            .unwrap();
+        let screen = screen_manager.screen_mut(0);
+        assert!(screen.focused_client().eq(&Some(frame_c)));
+
+        // ┌──────────┐┌──────────┐
+        // │ selected ││          │
+        // ├──────────┤│          │
+        // │          ││          │
+        // └──────────┘└──────────┘
+        // going back left should select the first master
+        TallLayout {}
+            .focus_client(&mut screen_manager, Direction::Left)
+            .unwrap();
         let screen = screen_manager.screen_mut(0);
         assert!(screen.focused_client().eq(&Some(frame_a)));
+
+        // masters can outnumber the clients, in which case every client is a master
+        let workspace = screen.active_workspace_mut();
+        workspace.change_master_count(5);
+        assert!(workspace.master_count().eq(&7));
+        workspace.change_master_count(-10);
+        assert!(workspace.master_count().eq(&0));
+    }
+
+    #[test]
+    fn test_focusing_ignores_floating_clients() {
+        let config = Rc::new(RefCell::new(Config::default()));
+        let root = unsafe { xcb::x::Window::new(0) };
+        let screen_positions = vec![Screen::new(&config, "", Position::new(0, 0, 100, 100))];
+        let mut screen_manager = ScreenManager::new(screen_positions, config, root);
+
+        let (frame_a, client_a) = create_fake_client();
+        let (frame_b, client_b) = create_fake_client();
+        let (frame_c, client_c) = create_fake_client();
+        screen_manager.create_client(frame_a, client_a, None, None);
+        screen_manager.create_client(frame_b, client_b, None, None);
+        screen_manager.create_client(frame_c, client_c, None, None);
+        screen_manager
+            .clients_mut()
+            .get_mut(&frame_a)
+            .unwrap()
+            .floating = true;
+
+        // ┌──────────┐┌──────────┐
+        // │ selected ││    c     │
+        // └──────────┘└──────────┘
+        // the floating client is not part of the layout, so b is the master
+        let workspace = screen_manager.screen_mut(0).active_workspace_mut();
+        workspace.set_focused_client(Some(frame_b));
+        TallLayout {}
+            .focus_client(&mut screen_manager, Direction::Right)
+            .unwrap();
+        assert!(screen_manager.screen(0).focused_client().eq(&Some(frame_c)));
+
+        // swapping the master with the side column skips over the floating client
+        screen_manager
+            .screen_mut(0)
+            .active_workspace_mut()
+            .set_focused_client(Some(frame_b));
+        TallLayout {}.move_client(&mut screen_manager, Direction::Right);
+        assert!(screen_manager
+            .screen(0)
+            .active_workspace()
+            .clients()
+            .eq(&[frame_a, frame_c, frame_b]));
+    }
+
+    #[test]
+    fn test_arrange_splits_master_and_side_columns() {
+        let config = Config::default();
+        let screen = Screen::new(
+            &Rc::new(RefCell::new(Config::default())),
+            "",
+            Position::new(0, 0, 100, 100),
+        );
+        let clients = [create_fake_client(), create_fake_client()]
+            .into_iter()
+            .map(|(frame, window)| Client {
+                frame,
+                window,
+                workspace: 0,
+                visible: true,
+                fullscreen: false,
+                above: false,
+                demands_attention: false,
+                floating: false,
+                floating_position: None,
+                no_border: false,
+            })
+            .collect::<Vec<_>>();
+        let clients = clients.iter().collect::<Vec<_>>();
+
+        let placements = TallLayout {}.arrange(&screen, &clients, &config);
+        let border_double = config.border_width() as u32 * 2;
+
+        assert!(placements.len().eq(&2));
+        assert!(placements[0].frame.eq(&Position::new(
+            0,
+            0,
+            50 - border_double,
+            100 - border_double
+        )));
+        assert!(placements[1].frame.eq(&Position::new(
+            50,
+            0,
+            50 - border_double,
+            100 - border_double
+        )));
+    }
+
+    #[test]
+    fn focus_and_move_continue_onto_the_adjacent_screen() {
+        let config = Rc::new(RefCell::new(Config::default()));
+        let root = unsafe { xcb::x::Window::new(0) };
+        let screen_positions = vec![
+            Screen::new(&config, "DP-1", Position::new(0, 0, 100, 100)),
+            Screen::new(&config, "DP-2", Position::new(100, 0, 100, 100)),
+        ];
+        let mut screen_manager = ScreenManager::new(screen_positions, config, root);
+
+        let (frame_a, client_a) = create_fake_client();
+        let (frame_b, client_b) = create_fake_client();
+        let (frame_c, client_c) = create_fake_client();
+        screen_manager.set_active_screen(1);
+        screen_manager.create_client(frame_b, client_b, None, None);
+        screen_manager.create_client(frame_c, client_c, None, None);
+        screen_manager.set_active_screen(0);
+        screen_manager.create_client(frame_a, client_a, None, None);
+
+        // ┌──────────┐┌─────┬────┐
+        // │ selected ││  b  │ c  │
+        // └──────────┘└─────┴────┘
+        // the master of the screen to the right is nearer than its focused client
+        let result = TallLayout {}
+            .focus_client(&mut screen_manager, Direction::Right)
+            .unwrap();
+        assert!(result.eq(&Some((Some(frame_a), Some(frame_b)))));
+        assert!(screen_manager.active_screen_idx().eq(&1));
+
+        // moving the master left carries it to the first screen, where it stays focused
+        let focused_client = TallLayout {}.move_client(&mut screen_manager, Direction::Left);
+        assert!(focused_client.eq(&Some(frame_b)));
+        assert!(screen_manager.active_screen_idx().eq(&0));
+        let screen = screen_manager.screen(0);
+        assert!(screen.active_workspace().clients().eq(&[frame_a, frame_b]));
+        assert!(screen.focused_client().eq(&Some(frame_b)));
+        assert!(screen_manager
+            .screen(1)
+            .active_workspace()
+            .clients()
+            .eq(&[frame_c]));
+    }
+
+    #[test]
+    fn gaps_larger_than_the_column_dont_underflow() {
+        let config = Rc::new(RefCell::new(Config::default()));
+        let root = unsafe { xcb::x::Window::new(0) };
+        let screen_positions = vec![Screen::new(&config, "", Position::new(0, 0, 100, 100))];
+        let mut screen_manager = ScreenManager::new(screen_positions, config.clone(), root);
+        for _ in 0..20 {
+            let (frame, client) = create_fake_client();
+            screen_manager.create_client(frame, client, None, None);
+        }
+
+        let screen = screen_manager.screen_mut(0);
+        let max_gap = screen.max_gap();
+        screen.active_workspace_mut().resize_gaps(1000, max_gap);
+        assert!(screen.active_workspace().inner_gap(2).eq(&max_gap));
+
+        let screen = screen_manager.screen(0);
+        let clients = screen
+            .active_workspace()
+            .clients()
+            .iter()
+            .filter_map(|frame| screen_manager.clients().get(frame))
+            .collect::<Vec<_>>();
+        let placements = TallLayout {}.arrange(screen, &clients, &config.borrow());
+        assert!(placements.len().eq(&20));
+        assert!(placements
+            .iter()
+            .all(|placement| placement.frame.height.lt(&100)));
     }
 }
@@ -1,10 +1,23 @@
+use crate::xcb_utils::*;
 use crate::{
-    atoms::Atoms, decorator::Decorator, event::EventContext, ewmh::ewmh_set_wm_hints,
-    handlers::Handlers, keyboard::Keyboard, layout_manager::LayoutManager, position::Position,
-    screen::Screen, screen_manager::ScreenManager,
+    atoms::Atoms,
+    config_watcher::ConfigWatcher,
+    decorator::Decorator,
+    event::EventContext,
+    ewmh::ewmh_set_wm_hints,
+    handlers::Handlers,
+    ipc_server::{self, IpcMessage, StateSnapshot, Subscribers},
+    keyboard::Keyboard,
+    layout_manager::LayoutManager,
+    mouse,
+    position::Position,
+    restart::{self, SavedState},
+    screen::Screen,
+    screen_manager::ScreenManager,
 };
 use anyhow::Context;
 use config::{AutoCommand, AvailableActions, Config};
+use ipc::{Request, Response};
 use std::{
     cell::RefCell,
     rc::Rc,
@@ -18,6 +31,13 @@ use xcb::{
     x::{self, ChangeWindowAttributes},
 };
 
+/// events we listen for on the root window, substructure redirection makes us the window manager
+/// while pointer events tell us which screen the pointer is on
+const ROOT_EVENT_MASK: x::EventMask = x::EventMask::SUBSTRUCTURE_REDIRECT
+    .union(x::EventMask::SUBSTRUCTURE_NOTIFY)
+    .union(x::EventMask::ENTER_WINDOW)
+    .union(x::EventMask::POINTER_MOTION);
+
 pub struct Lucky {
     conn: Arc<xcb::Connection>,
     keyboard: Keyboard,
@@ -27,29 +47,51 @@ pub struct Lucky {
     atoms: Atoms,
     layout_manager: LayoutManager,
     decorator: Decorator,
-    last_pointer_position: (i16, i16),
+    subscribers: Subscribers,
+    /// state last published to ipc subscribers
+    snapshot: StateSnapshot,
+    config_watcher: Option<ConfigWatcher>,
 }
 
 impl Lucky {
     #[tracing::instrument(skip_all, err)]
     pub fn new() -> anyhow::Result<Self> {
-        let (conn, _) = xcb::Connection::connect(None).context("failed to initialize self.conn to the X server. Check the DISPLAY environment variable")?;
+        // randr events are only resolved for extensions requested when connecting
+        let (conn, _) = xcb::Connection::connect_with_extensions(None, &[xcb::Extension::RandR], &[]).context("failed to initialize self.conn to the X server. Check the DISPLAY environment variable")?;
         let conn = Arc::new(conn);
-        let config = Rc::new(RefCell::new(config::load_config()));
-        execute_auto_commands(config.borrow().startup_commands())
-            .context("failed to run startup commands")?;
+        let (config, config_error) = match config::load_config() {
+            Ok(config) => (config, None),
+            Err(e) => {
+                tracing::error!("using fallback configuration: {e:?}");
+                (Config::fallback(), Some(e))
+            }
+        };
+        let config = Rc::new(RefCell::new(config));
+
+        // whatever was started before restarting is still running
+        if !SavedState::exists() {
+            execute_auto_commands(config.borrow().startup_commands())
+                .context("failed to run startup commands")?;
+        }
 
         let root = Self::setup(&conn)?;
         let atoms = Atoms::new(&conn);
-        let screens = Self::get_monitors(&conn, root, &config)?;
+        let screens = get_monitors(&conn, root)?
+            .into_iter()
+            .map(|(name, position)| Screen::new(&config, &name, position))
+            .collect::<Vec<_>>();
         let screen_manager = ScreenManager::new(screens, config.clone(), root);
+        mouse::grab_buttons(&conn, &config, root).context("failed to grab mouse buttons")?;
 
         screen_manager.update_atoms(&atoms, &conn);
         ewmh_set_wm_hints(&conn, root, &atoms).context("failed to setup window manager hints")?;
+        report_config_error(&conn, root, &atoms, config_error.as_ref());
 
         conn.flush().expect("failed to flush the connection");
 
         Ok(Lucky {
+            snapshot: StateSnapshot::new(&screen_manager),
+            subscribers: Subscribers::default(),
             keyboard: Keyboard::new(&conn, config.clone(), root)?,
             layout_manager: LayoutManager::new(conn.clone(), config.clone()),
             decorator: Decorator::new(conn.clone(), config.clone()),
@@ -59,101 +101,240 @@ impl Lucky {
 
             conn,
             config,
-            last_pointer_position: (0, 0),
+            config_watcher: None,
         })
     }
 
     pub fn run(mut self) -> anyhow::Result<()> {
-        let (event_tx, event_rx) = channel::<XEvent>();
-        let (action_tx, action_rx) = channel::<AvailableActions>();
+        let (tx, rx) = channel::<Message>();
+
+        // scripts and status bars can live without the socket, but the window manager shouldn't
+        if let Err(e) = ipc_server::listen(tx.clone(), self.subscribers.clone()) {
+            tracing::error!("failed to start ipc server: {e:?}");
+        }
+
+        self.adopt_windows(&tx)
+            .context("failed to adopt existing windows")?;
+
+        self.watch_config(&tx);
 
         let conn = self.conn.clone();
-        let event_tx_c = event_tx.clone();
+        let event_tx = tx.clone();
         std::thread::spawn(move || {
-            if poll_events(conn.clone(), event_tx_c).is_err() {
+            if poll_events(conn.clone(), event_tx).is_err() {
                 std::process::abort();
             }
         });
 
+        // every source of work sends through the same channel, so we sleep until one of them
+        // has something for us instead of polling each of them
         loop {
-            if let Ok(AvailableActions::Reload) = action_rx.try_recv() {
-                self.config.borrow_mut().update(config::load_config());
-                self.layout_manager
-                    .display_screens(&self.screen_manager, &self.decorator)
-                    .expect("failed to redraw the screen");
-            }
+            let message = rx.recv().context("every message sender was dropped")?;
 
-            let pointer_cookie = self.conn.send_request(&xcb::x::QueryPointer {
-                window: self
-                    .conn
-                    .get_setup()
-                    .roots()
-                    .next()
-                    .expect("should have at least one screen")
-                    .root(),
-            });
+            match message {
+                Message::Action(AvailableActions::Reload) => {
+                    match self.reload(&tx) {
+                        Ok(_) => ipc_server::publish(&self.subscribers, ipc::Event::ConfigReloaded),
+                        Err(e) => tracing::error!("failed to reload configuration: {e:?}"),
+                    }
+                    self.publish_events();
+                }
+                Message::Action(AvailableActions::Restart) => {
+                    if let Err(e) = self.restart(&tx) {
+                        tracing::error!("failed to restart: {e:?}");
+                    }
+                    self.publish_events();
+                }
+                Message::Action(_) => {}
+                Message::Ipc(message) => {
+                    let response = self.handle_ipc_request(message.request, &tx);
+                    message.reply_tx.send(response).ok();
+                    self.conn.flush().expect("failed to flush the connection");
+                    self.publish_events();
+                }
+                Message::X(event) => {
+                    self.handle_event(event, &tx)?;
+                    self.conn.flush().expect("failed to flush the connection");
+                    self.publish_events();
+                }
+            }
+        }
+    }
 
-            if let Ok(pointer_reply) = self.conn.wait_for_reply(pointer_cookie) {
-                let pointer_position = (pointer_reply.root_x(), pointer_reply.root_y());
-                if pointer_position.ne(&self.last_pointer_position) {
-                    self.last_pointer_position = pointer_position;
-                    self.screen_manager.borrow_mut().maybe_switch_screen(
-                        pointer_reply,
-                        &self.conn,
-                        &self.atoms,
-                    );
+    /// Dispatches an event from the X server to every handler interested in it
+    fn handle_event(&mut self, event: XEvent, tx: &Sender<Message>) -> anyhow::Result<()> {
+        match event {
+            XEvent::KeyPress(event) => self.handlers.on_key_press(EventContext {
+                event,
+                conn: self.conn.clone(),
+                keyboard: &self.keyboard,
+                config: self.config.clone(),
+                screen_manager: self.screen_manager.clone(),
+                atoms: &self.atoms,
+                decorator: &self.decorator,
+                layout_manager: &self.layout_manager,
+                action_tx: tx.clone(),
+            })?,
+            XEvent::MapRequest(event) => self.handlers.on_map_request(EventContext {
+                event,
+                conn: self.conn.clone(),
+                keyboard: &self.keyboard,
+                config: self.config.clone(),
+                screen_manager: self.screen_manager.clone(),
+                atoms: &self.atoms,
+                decorator: &self.decorator,
+                layout_manager: &self.layout_manager,
+                action_tx: tx.clone(),
+            })?,
+            XEvent::DestroyNotify(event) => self.handlers.on_destroy_notify(EventContext {
+                event,
+                conn: self.conn.clone(),
+                keyboard: &self.keyboard,
+                config: self.config.clone(),
+                screen_manager: self.screen_manager.clone(),
+                atoms: &self.atoms,
+                decorator: &self.decorator,
+                layout_manager: &self.layout_manager,
+                action_tx: tx.clone(),
+            })?,
+            XEvent::EnterNotify(event) => self.handlers.on_enter_notify(EventContext {
+                event,
+                conn: self.conn.clone(),
+                keyboard: &self.keyboard,
+                config: self.config.clone(),
+                screen_manager: self.screen_manager.clone(),
+                atoms: &self.atoms,
+                decorator: &self.decorator,
+                layout_manager: &self.layout_manager,
+                action_tx: tx.clone(),
+            })?,
+            XEvent::UnmapNotify(event) => self.handlers.on_unmap_notify(EventContext {
+                event,
+                conn: self.conn.clone(),
+                keyboard: &self.keyboard,
+                config: self.config.clone(),
+                screen_manager: self.screen_manager.clone(),
+                atoms: &self.atoms,
+                decorator: &self.decorator,
+                layout_manager: &self.layout_manager,
+                action_tx: tx.clone(),
+            })?,
+            XEvent::PropertyNotify(event) => self.handlers.on_property_notify(EventContext {
+                event,
+                conn: self.conn.clone(),
+                keyboard: &self.keyboard,
+                config: self.config.clone(),
+                screen_manager: self.screen_manager.clone(),
+                atoms: &self.atoms,
+                decorator: &self.decorator,
+                layout_manager: &self.layout_manager,
+                action_tx: tx.clone(),
+            })?,
+            XEvent::ClientMessage(event) => self.handlers.on_client_message(EventContext {
+                event,
+                conn: self.conn.clone(),
+                keyboard: &self.keyboard,
+                config: self.config.clone(),
+                screen_manager: self.screen_manager.clone(),
+                atoms: &self.atoms,
+                decorator: &self.decorator,
+                layout_manager: &self.layout_manager,
+                action_tx: tx.clone(),
+            })?,
+            XEvent::ConfigureRequest(event) => {
+                self.handlers.on_configure_request(EventContext {
+                    event,
+                    conn: self.conn.clone(),
+                    keyboard: &self.keyboard,
+                    config: self.config.clone(),
+                    screen_manager: self.screen_manager.clone(),
+                    atoms: &self.atoms,
+                    decorator: &self.decorator,
+                    layout_manager: &self.layout_manager,
+                    action_tx: tx.clone(),
+                })?
+            }
+            XEvent::ButtonPress(event) => self.handlers.on_button_press(EventContext {
+                event,
+                conn: self.conn.clone(),
+                keyboard: &self.keyboard,
+                config: self.config.clone(),
+                screen_manager: self.screen_manager.clone(),
+                atoms: &self.atoms,
+                decorator: &self.decorator,
+                layout_manager: &self.layout_manager,
+                action_tx: tx.clone(),
+            })?,
+            XEvent::ButtonRelease(event) => self.handlers.on_button_release(EventContext {
+                event,
+                conn: self.conn.clone(),
+                keyboard: &self.keyboard,
+                config: self.config.clone(),
+                screen_manager: self.screen_manager.clone(),
+                atoms: &self.atoms,
+                decorator: &self.decorator,
+                layout_manager: &self.layout_manager,
+                action_tx: tx.clone(),
+            })?,
+            XEvent::MotionNotify(event) => self.handlers.on_motion_notify(EventContext {
+                event,
+                conn: self.conn.clone(),
+                keyboard: &self.keyboard,
+                config: self.config.clone(),
+                screen_manager: self.screen_manager.clone(),
+                atoms: &self.atoms,
+                decorator: &self.decorator,
+                layout_manager: &self.layout_manager,
+                action_tx: tx.clone(),
+            })?,
+            XEvent::MonitorsChanged => {
+                if let Err(e) = self.update_monitors() {
+                    tracing::error!("failed to update monitors: {e:?}");
                 }
             }
+        }
 
-            if let Ok(event) = event_rx.try_recv() {
-                match event {
-                    XEvent::KeyPress(event) => self.handlers.on_key_press(EventContext {
-                        event,
-                        conn: self.conn.clone(),
-                        keyboard: &self.keyboard,
-                        config: self.config.clone(),
-                        screen_manager: self.screen_manager.clone(),
-                        atoms: &self.atoms,
-                        decorator: &self.decorator,
-                        layout_manager: &self.layout_manager,
-                        action_tx: action_tx.clone(),
-                    })?,
-                    XEvent::MapRequest(event) => self.handlers.on_map_request(EventContext {
-                        event,
-                        conn: self.conn.clone(),
-                        keyboard: &self.keyboard,
-                        config: self.config.clone(),
-                        screen_manager: self.screen_manager.clone(),
-                        atoms: &self.atoms,
-                        decorator: &self.decorator,
-                        layout_manager: &self.layout_manager,
-                        action_tx: action_tx.clone(),
-                    })?,
-                    XEvent::DestroyNotify(event) => {
-                        self.handlers.on_destroy_notify(EventContext {
-                            event,
-                            conn: self.conn.clone(),
-                            keyboard: &self.keyboard,
-                            config: self.config.clone(),
-                            screen_manager: self.screen_manager.clone(),
-                            atoms: &self.atoms,
-                            decorator: &self.decorator,
-                            layout_manager: &self.layout_manager,
-                            action_tx: action_tx.clone(),
-                        })?
-                    }
-                    XEvent::EnterNotify(event) => self.handlers.on_enter_notify(EventContext {
-                        event,
-                        conn: self.conn.clone(),
-                        keyboard: &self.keyboard,
-                        config: self.config.clone(),
-                        screen_manager: self.screen_manager.clone(),
-                        atoms: &self.atoms,
-                        decorator: &self.decorator,
-                        layout_manager: &self.layout_manager,
-                        action_tx: action_tx.clone(),
-                    })?,
-                    XEvent::UnmapNotify(event) => self.handlers.on_unmap_notify(EventContext {
+        Ok(())
+    }
+
+    /// Notifies ipc subscribers of everything that changed since the last time we did
+    fn publish_events(&mut self) {
+        let snapshot = StateSnapshot::new(&self.screen_manager.borrow());
+        for event in self.snapshot.changes(&snapshot) {
+            ipc_server::publish(&self.subscribers, event);
+        }
+        self.snapshot = snapshot;
+    }
+
+    fn handle_ipc_request(&mut self, request: Request, action_tx: &Sender<Message>) -> Response {
+        match request {
+            Request::Action { action } => {
+                let Ok(action) = serde_json::from_value::<AvailableActions>(action.as_str().into())
+                else {
+                    return Response::Error {
+                        message: format!("invalid action: {action}"),
+                    };
+                };
+
+                let root = self.screen_manager.borrow().root();
+                // actions are handled the same way as when their keys are pressed, so we
+                // pretend a key was pressed on the root window
+                let event = xcb::x::KeyPressEvent::new(
+                    0,
+                    xcb::x::CURRENT_TIME,
+                    root,
+                    root,
+                    xcb::x::WINDOW_NONE,
+                    0,
+                    0,
+                    0,
+                    0,
+                    xcb::x::KeyButMask::empty(),
+                    true,
+                );
+
+                let result = self.handlers.on_action(
+                    EventContext {
                         event,
                         conn: self.conn.clone(),
                         keyboard: &self.keyboard,
@@ -163,29 +344,183 @@ impl Lucky {
                         decorator: &self.decorator,
                         layout_manager: &self.layout_manager,
                         action_tx: action_tx.clone(),
-                    })?,
-                    XEvent::PropertyNotify(event) => {
-                        self.handlers.on_property_notify(EventContext {
-                            event,
-                            conn: self.conn.clone(),
-                            keyboard: &self.keyboard,
-                            config: self.config.clone(),
-                            screen_manager: self.screen_manager.clone(),
-                            atoms: &self.atoms,
-                            decorator: &self.decorator,
-                            layout_manager: &self.layout_manager,
-                            action_tx: action_tx.clone(),
-                        })?
-                    }
-                    XEvent::ConfigureRequest(_) => todo!(),
-                };
+                    },
+                    action,
+                );
 
-                self.conn.flush().expect("failed to flush the connection");
+                match result {
+                    Ok(_) => Response::Ok,
+                    Err(e) => Response::Error {
+                        message: format!("{e:?}"),
+                    },
+                }
             }
+            Request::GetWorkspaces => ipc_server::workspaces(&self.screen_manager.borrow()),
+            Request::GetClients => ipc_server::clients(&self.screen_manager.borrow()),
+            Request::GetScreens => ipc_server::screens(&self.screen_manager.borrow()),
+            // subscriptions are kept by the connection itself, and never reach the main loop
+            Request::Subscribe => Response::Ok,
         }
     }
 
+    /// Replaces the running process with a fresh instance of lucky. Nothing is touched unless
+    /// the new instance can be executed, and if executing it still fails, the clients that were
+    /// already released are adopted again, so we can keep running either way
+    fn restart(&mut self, action_tx: &Sender<Message>) -> anyhow::Result<()> {
+        let program = restart::executable()?;
+        SavedState::new(&self.screen_manager.borrow())
+            .save()
+            .context("failed to save state before restarting")?;
+
+        let e = restart::restart(&self.conn, &mut self.screen_manager.borrow_mut(), &program);
+        tracing::error!("failed to execute {}: {e:?}", program.display());
+
+        self.adopt_windows(action_tx)
+            .context("failed to adopt windows again")?;
+        self.conn.flush()?;
+
+        Ok(())
+    }
+
+    /// Watches the configuration file while `auto_reload` is enabled, and stops watching it
+    /// otherwise
+    fn watch_config(&mut self, action_tx: &Sender<Message>) {
+        match self.config.borrow().auto_reload() {
+            true if self.config_watcher.is_none() => {
+                self.config_watcher =
+                    config::config_path().map(|path| ConfigWatcher::new(path, action_tx.clone()));
+            }
+            true => {}
+            false => self.config_watcher = None,
+        }
+    }
+
+    /// Loads the configuration file again, applying every change to what is already on screen.
+    /// A configuration that fails to load is ignored, keeping the current one
+    fn reload(&mut self, action_tx: &Sender<Message>) -> anyhow::Result<()> {
+        let root = self.screen_manager.borrow().root();
+        let config = config::load_config();
+        report_config_error(&self.conn, root, &self.atoms, config.as_ref().err());
+        let config = config.context("keeping the current configuration")?;
+        self.config.borrow_mut().update(config);
+
+        self.watch_config(action_tx);
+
+        self.keyboard
+            .regrab_keys(&self.conn, &self.config.borrow(), root)
+            .context("failed to grab keys")?;
+        mouse::regrab_buttons(&self.conn, &self.config, root)
+            .context("failed to grab mouse buttons")?;
+
+        let mut screen_manager = self.screen_manager.borrow_mut();
+        screen_manager.resize_workspaces();
+        screen_manager.arrange_shared_workspaces();
+        self.layout_manager
+            .hide_inactive_workspaces(&screen_manager);
+        self.layout_manager
+            .redecorate_clients(&screen_manager, &self.decorator)?;
+        screen_manager.update_atoms(&self.atoms, &self.conn);
+        drop(screen_manager);
+
+        self.layout_manager
+            .display_screens(&self.screen_manager, &self.decorator)
+            .context("failed to redraw the screen")?;
+        self.conn.flush()?;
+
+        Ok(())
+    }
+
+    /// Manages every window that was already mapped before we started, such as windows that
+    /// survived a crash or a restart, as if they had just requested to be mapped.
+    ///
+    /// When restarting, clients are also moved back to where they were before the restart
     #[tracing::instrument(skip_all, err)]
+    fn adopt_windows(&mut self, action_tx: &Sender<Message>) -> anyhow::Result<()> {
+        let root = self.screen_manager.borrow().root();
+        let saved_state = SavedState::load();
+        let saved_windows = saved_state
+            .iter()
+            .flat_map(|state| state.windows())
+            .collect::<Vec<_>>();
+        let tree = self
+            .conn
+            .wait_for_reply(self.conn.send_request(&x::QueryTree { window: root }))
+            .context("failed to query the windows of the root")?;
+
+        let windows = tree
+            .children()
+            .iter()
+            .filter(|window| {
+                // clients on hidden workspaces aren't viewable, but we still know about them
+                saved_windows.contains(window)
+                    || self
+                        .conn
+                        .wait_for_reply(
+                            self.conn
+                                .send_request(&x::GetWindowAttributes { window: **window }),
+                        )
+                        .is_ok_and(|attributes| {
+                            attributes.map_state().eq(&x::MapState::Viewable)
+                                && !attributes.override_redirect()
+                        })
+            })
+            .copied()
+            .collect::<Vec<_>>();
+
+        // reparenting a mapped window unmaps it, which would otherwise be handled as the window
+        // being closed, so we stop listening for those while adopting windows
+        self.conn.send_request(&ChangeWindowAttributes {
+            window: root,
+            value_list: &[x::Cw::EventMask(x::EventMask::SUBSTRUCTURE_REDIRECT)],
+        });
+
+        // a single window failing to be adopted shouldn't leave every other one unmanaged
+        for window in windows {
+            tracing::debug!("adopting existing window {window:?}");
+            let result = self.handlers.on_map_request(EventContext {
+                event: x::MapRequestEvent::new(root, window),
+                conn: self.conn.clone(),
+                keyboard: &self.keyboard,
+                config: self.config.clone(),
+                screen_manager: self.screen_manager.clone(),
+                atoms: &self.atoms,
+                decorator: &self.decorator,
+                layout_manager: &self.layout_manager,
+                action_tx: action_tx.clone(),
+            });
+            if let Err(e) = result {
+                tracing::error!("failed to adopt window {window:?}: {e:?}");
+            }
+        }
+
+        self.conn.send_request(&ChangeWindowAttributes {
+            window: root,
+            value_list: &[x::Cw::EventMask(ROOT_EVENT_MASK)],
+        });
+
+        if let Some(saved_state) = saved_state {
+            let mut screen_manager = self.screen_manager.borrow_mut();
+            saved_state.restore(&mut screen_manager);
+            for screen in screen_manager.screens() {
+                screen
+                    .workspaces()
+                    .iter()
+                    .filter(|workspace| workspace.id().ne(&screen.active_workspace().id()))
+                    .for_each(|workspace| self.layout_manager.hide_workspace(workspace));
+            }
+            drop(screen_manager);
+            self.layout_manager
+                .display_screens(&self.screen_manager, &self.decorator)?;
+            self.screen_manager
+                .borrow()
+                .update_atoms(&self.atoms, &self.conn);
+        }
+
+        self.conn.flush()?;
+
+        Ok(())
+    }
+
     fn setup(conn: &Arc<xcb::Connection>) -> anyhow::Result<xcb::x::Window> {
         let screen = conn
             .get_setup()
@@ -219,71 +554,143 @@ impl Lucky {
 
         conn.check_request(conn.send_request_checked(&ChangeWindowAttributes {
             window: root,
-            value_list: &[
-                x::Cw::EventMask(
-                    x::EventMask::SUBSTRUCTURE_REDIRECT | x::EventMask::SUBSTRUCTURE_NOTIFY,
-                ),
-                x::Cw::Cursor(cursor),
-            ],
+            value_list: &[x::Cw::EventMask(ROOT_EVENT_MASK), x::Cw::Cursor(cursor)],
         }))
         .context("failed to subscribe for substructure redirection")?;
 
+        conn.check_request(conn.send_request_checked(&randr::SelectInput {
+            window: root,
+            enable: randr::NotifyMask::SCREEN_CHANGE
+                | randr::NotifyMask::OUTPUT_CHANGE
+                | randr::NotifyMask::CRTC_CHANGE,
+        }))
+        .context("failed to subscribe for monitor changes")?;
+
         Ok(root)
     }
 
-    fn get_monitors(
-        conn: &Arc<xcb::Connection>,
-        root: xcb::x::Window,
-        config: &Rc<RefCell<Config>>,
-    ) -> anyhow::Result<Vec<Screen>> {
-        let total_screens = conn
-            .wait_for_reply(conn.send_request(&randr::GetMonitors {
-                window: root,
-                get_active: true,
-            }))
-            .context("failed to get monitors")?;
-
-        let screens = total_screens
-            .monitors()
-            .map(Into::into)
-            .collect::<Vec<Position>>()
-            .into_iter()
-            .map(|position| Screen::new(config, position))
-            .collect::<Vec<_>>();
+    /// Monitors were plugged, unplugged or changed resolution, so screens are updated to
+    /// match them, and every screen is laid out again
+    fn update_monitors(&mut self) -> anyhow::Result<()> {
+        let root = self.screen_manager.borrow().root();
+        let monitors = get_monitors(&self.conn, root)?;
+        tracing::debug!("monitors changed: {monitors:?}");
 
-        Ok(screens)
+        let mut screen_manager = self.screen_manager.borrow_mut();
+        screen_manager.update_screens(monitors);
+
+        // clients from removed screens might have been visible, but only the active
+        // workspaces should be
+        self.layout_manager
+            .hide_inactive_workspaces(&screen_manager);
+        screen_manager.update_atoms(&self.atoms, &self.conn);
+        drop(screen_manager);
+
+        self.layout_manager
+            .display_screens(&self.screen_manager, &self.decorator)?;
+        self.conn.flush()?;
+
+        Ok(())
     }
 }
 
+/// name and position of every active monitor, as reported by randr
+fn get_monitors(
+    conn: &Arc<xcb::Connection>,
+    root: xcb::x::Window,
+) -> anyhow::Result<Vec<(String, Position)>> {
+    let monitors = conn
+        .wait_for_reply(conn.send_request(&randr::GetMonitors {
+            window: root,
+            get_active: true,
+        }))
+        .context("failed to get monitors")?;
+
+    monitors
+        .monitors()
+        .map(|monitor| {
+            let name = conn
+                .wait_for_reply(conn.send_request(&xcb::x::GetAtomName {
+                    atom: monitor.name(),
+                }))
+                .context("failed to get monitor name")?;
+            Ok((name.name().to_string(), monitor.into()))
+        })
+        .collect()
+}
+
 #[tracing::instrument(skip_all, err)]
-fn poll_events(conn: Arc<xcb::Connection>, event_tx: Sender<XEvent>) -> anyhow::Result<()> {
+fn poll_events(conn: Arc<xcb::Connection>, event_tx: Sender<Message>) -> anyhow::Result<()> {
+    // event read from the queue while compressing motion events that still has to be handled
+    let mut pending_event = None;
+
     loop {
-        if let Ok(event) = conn.wait_for_event() {
+        let event = match pending_event.take() {
+            Some(event) => Ok(event),
+            None => conn.wait_for_event(),
+        };
+
+        if let Ok(event) = event {
             match event {
                 xcb::Event::X(xcb::x::Event::KeyPress(e)) => event_tx
-                    .send(XEvent::KeyPress(e))
+                    .send(Message::X(XEvent::KeyPress(e)))
                     .context("failed to send event through channel")?,
                 xcb::Event::X(xcb::x::Event::MapRequest(e)) => event_tx
-                    .send(XEvent::MapRequest(e))
+                    .send(Message::X(XEvent::MapRequest(e)))
                     .context("failed to send event through channel")?,
                 xcb::Event::X(xcb::x::Event::DestroyNotify(e)) => event_tx
-                    .send(XEvent::DestroyNotify(e))
+                    .send(Message::X(XEvent::DestroyNotify(e)))
                     .context("failed to send event through channel")?,
                 xcb::Event::X(xcb::x::Event::EnterNotify(e)) => event_tx
-                    .send(XEvent::EnterNotify(e))
+                    .send(Message::X(XEvent::EnterNotify(e)))
                     .context("failed to send event through channel")?,
                 xcb::Event::X(xcb::x::Event::UnmapNotify(e)) => event_tx
-                    .send(XEvent::UnmapNotify(e))
+                    .send(Message::X(XEvent::UnmapNotify(e)))
                     .context("failed to send event through channel")?,
                 xcb::Event::X(xcb::x::Event::PropertyNotify(e)) => event_tx
-                    .send(XEvent::PropertyNotify(e))
+                    .send(Message::X(XEvent::PropertyNotify(e)))
                     .context("failed to send event through channel")?,
-                xcb::Event::X(xcb::x::Event::ConfigureRequest(_)) => {}
+                xcb::Event::X(xcb::x::Event::ClientMessage(e)) => event_tx
+                    .send(Message::X(XEvent::ClientMessage(e)))
+                    .context("failed to send event through channel")?,
+                xcb::Event::X(xcb::x::Event::ConfigureRequest(e)) => event_tx
+                    .send(Message::X(XEvent::ConfigureRequest(e)))
+                    .context("failed to send event through channel")?,
+                xcb::Event::X(xcb::x::Event::ButtonPress(e)) => event_tx
+                    .send(Message::X(XEvent::ButtonPress(e)))
+                    .context("failed to send event through channel")?,
+                xcb::Event::X(xcb::x::Event::ButtonRelease(e)) => event_tx
+                    .send(Message::X(XEvent::ButtonRelease(e)))
+                    .context("failed to send event through channel")?,
+                xcb::Event::X(xcb::x::Event::MotionNotify(e)) => {
+                    // while dragging, the X server sends far more motion events than we can
+                    // handle, only the last one queued matters, so we skip the others
+                    let mut latest = e;
+                    while let Ok(Some(event)) = conn.poll_for_queued_event() {
+                        match event {
+                            xcb::Event::X(xcb::x::Event::MotionNotify(e)) => latest = e,
+                            event => {
+                                pending_event = Some(event);
+                                break;
+                            }
+                        }
+                    }
+
+                    event_tx
+                        .send(Message::X(XEvent::MotionNotify(latest)))
+                        .context("failed to send event through channel")?
+                }
                 xcb::Event::RandR(xcb::randr::Event::Notify(e)) => {
-                    tracing::trace!("from notify randr {e:?}")
+                    tracing::trace!("from notify randr {e:?}");
+                    event_tx
+                        .send(Message::X(XEvent::MonitorsChanged))
+                        .context("failed to send event through channel")?
                 }
                 xcb::Event::RandR(xcb::randr::Event::ScreenChangeNotify(e)) => {
-                    tracing::trace!("from change screen {e:?}")
+                    tracing::trace!("from change screen {e:?}");
+                    event_tx
+                        .send(Message::X(XEvent::MonitorsChanged))
+                        .context("failed to send event through channel")?
                 }
                 _ => {}
             };
@@ -292,6 +699,36 @@ fn poll_events(conn: Arc<xcb::Connection>, event_tx: Sender<XEvent>) -> anyhow::
     }
 }
 
+/// Exposes why the configuration failed to load through `_LUCKY_CONFIG_ERROR` on the root
+/// window, so status bars and scripts can show it. The property is removed once the
+/// configuration loads successfully
+fn report_config_error(
+    conn: &Arc<xcb::Connection>,
+    root: xcb::x::Window,
+    atoms: &Atoms,
+    error: Option<&anyhow::Error>,
+) {
+    match error {
+        Some(error) => {
+            xcb_change_prop!(
+                conn,
+                root,
+                x::PropMode::Replace,
+                x::ATOM_STRING,
+                atoms.lucky_config_error,
+                format!("{error:#}").as_bytes()
+            )
+            .ok();
+        }
+        None => {
+            conn.send_request(&x::DeleteProperty {
+                window: root,
+                property: atoms.lucky_config_error,
+            });
+        }
+    }
+}
+
 #[tracing::instrument(skip_all, err)]
 pub fn execute_auto_commands(auto_commands: &[AutoCommand]) -> anyhow::Result<()> {
     for command in auto_commands {
@@ -314,4 +751,21 @@ pub enum XEvent {
     UnmapNotify(xcb::x::UnmapNotifyEvent),
     PropertyNotify(xcb::x::PropertyNotifyEvent),
     ConfigureRequest(xcb::x::ConfigureRequestEvent),
+    ClientMessage(xcb::x::ClientMessageEvent),
+    ButtonPress(xcb::x::ButtonPressEvent),
+    ButtonRelease(xcb::x::ButtonReleaseEvent),
+    MotionNotify(xcb::x::MotionNotifyEvent),
+    /// any randr notification, which might mean monitors were added, removed or resized
+    MonitorsChanged,
+}
+
+/// Everything the main loop reacts to, sent through a single channel so it can block until
+/// there is work to do
+pub enum Message {
+    /// an event from the X server
+    X(XEvent),
+    /// an action lucky performs by itself, such as reloading the configuration
+    Action(AvailableActions),
+    /// a request from an ipc client
+    Ipc(IpcMessage),
 }
@@ -1,21 +1,37 @@
 mod atoms;
+mod config_watcher;
 mod decorator;
 mod event;
 mod ewmh;
 mod handlers;
+mod ipc_server;
 mod keyboard;
 mod layout_manager;
 mod lucky;
+mod mouse;
 mod position;
+mod restart;
+mod rules;
 mod screen;
 mod screen_manager;
 
 mod xcb_utils;
 
 use lucky::Lucky;
+use std::path::PathBuf;
 use tracing_subscriber::FmtSubscriber;
 
 fn main() -> anyhow::Result<()> {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    match args.first().map(String::as_str) {
+        Some("--check-config") => check_config(args.get(1)),
+        Some(arg) => {
+            eprintln!("unknown argument: {arg}\n\nusage: lucky [--check-config [path]]");
+            std::process::exit(2);
+        }
+        None => {}
+    }
+
     let (data_dir, log_file) = config::log_file()?;
     let log_writer = tracing_appender::rolling::daily(data_dir, log_file);
     let (non_blocking, _guard) = tracing_appender::non_blocking(log_writer);
@@ -31,3 +47,22 @@ fn main() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Validates the configuration file at `path`, or the one lucky would load, and exits
+fn check_config(path: Option<&String>) -> ! {
+    let Some(path) = path.map(PathBuf::from).or_else(config::config_path) else {
+        eprintln!("could not find a configuration file to check");
+        std::process::exit(1);
+    };
+
+    let problems = config::check_config(&path);
+    if problems.is_empty() {
+        println!("{}: configuration is valid", path.display());
+        std::process::exit(0);
+    }
+
+    for problem in problems.iter() {
+        eprintln!("{}: {problem}", path.display());
+    }
+    std::process::exit(1);
+}
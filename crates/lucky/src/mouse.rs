@@ -0,0 +1,56 @@
+use config::Config;
+use std::{cell::RefCell, rc::Rc, sync::Arc};
+use xcb::x::{ButtonIndex, EventMask, GrabButton, GrabMode, ModMask, UngrabButton};
+
+/// Releases every button we grabbed and grabs the ones of the current configuration
+pub fn regrab_buttons(
+    conn: &Arc<xcb::Connection>,
+    config: &Rc<RefCell<Config>>,
+    root: xcb::x::Window,
+) -> anyhow::Result<()> {
+    conn.check_request(conn.send_request_checked(&UngrabButton {
+        button: ButtonIndex::Any,
+        grab_window: root,
+        modifiers: ModMask::ANY,
+    }))?;
+
+    grab_buttons(conn, config, root)
+}
+
+/// Grabs every button combination defined on `mouse_bindings` on the root window, so we
+/// receive the events regardless of which client is under the cursor.
+pub fn grab_buttons(
+    conn: &Arc<xcb::Connection>,
+    config: &Rc<RefCell<Config>>,
+    root: xcb::x::Window,
+) -> anyhow::Result<()> {
+    for binding in config.borrow().mouse_bindings() {
+        let button = match binding.button() {
+            1 => ButtonIndex::N1,
+            2 => ButtonIndex::N2,
+            3 => ButtonIndex::N3,
+            4 => ButtonIndex::N4,
+            5 => ButtonIndex::N5,
+            button => anyhow::bail!("failed to grab invalid mouse button: {button}"),
+        };
+
+        conn.check_request(
+            conn.send_request_checked(&GrabButton {
+                owner_events: false,
+                grab_window: root,
+                event_mask: EventMask::BUTTON_PRESS
+                    | EventMask::BUTTON_RELEASE
+                    | EventMask::BUTTON_MOTION,
+                pointer_mode: GrabMode::Async,
+                keyboard_mode: GrabMode::Async,
+                confine_to: xcb::x::WINDOW_NONE,
+                cursor: xcb::x::CURSOR_NONE,
+                button,
+                modifiers: ModMask::from_bits(binding.modifiers())
+                    .expect("no invalid modifiers should exist at this point"),
+            }),
+        )?;
+    }
+
+    Ok(())
+}
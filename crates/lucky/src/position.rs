@@ -17,6 +17,17 @@ impl From<&xcb::randr::MonitorInfo> for Position {
     }
 }
 
+impl From<&xcb::x::GetGeometryReply> for Position {
+    fn from(value: &xcb::x::GetGeometryReply) -> Self {
+        Position {
+            x: value.x().into(),
+            y: value.y().into(),
+            width: value.width().into(),
+            height: value.height().into(),
+        }
+    }
+}
+
 impl Position {
     pub fn new(x: i32, y: i32, width: u32, height: u32) -> Self {
         Position {
@@ -46,6 +57,24 @@ impl Position {
     pub fn top(&self) -> i32 {
         self.y
     }
+
+    /// the point at the middle of this position
+    pub fn center(&self) -> (i32, i32) {
+        (
+            self.x + self.width as i32 / 2,
+            self.y + self.height as i32 / 2,
+        )
+    }
+
+    /// a position with the given size, centered over this one
+    pub fn centered(&self, width: u32, height: u32) -> Position {
+        Position::new(
+            self.x + (self.width as i32 - width as i32) / 2,
+            self.y + (self.height as i32 - height as i32) / 2,
+            width,
+            height,
+        )
+    }
 }
 
 impl std::fmt::Display for Position {
@@ -0,0 +1,287 @@
+use crate::screen::WorkspaceLayout;
+use crate::screen_manager::ScreenManager;
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use xcb::{Xid, XidNew};
+
+/// Everything needed to put clients back where they were after restarting.
+///
+/// Frames don't survive a restart, so clients are identified by their windows
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SavedState {
+    active_screen: usize,
+    screens: Vec<SavedScreen>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct SavedScreen {
+    active_workspace: u8,
+    workspaces: Vec<SavedWorkspace>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct SavedWorkspace {
+    layout: WorkspaceLayout,
+    master_ratio: f32,
+    master_count: usize,
+    clients: Vec<u32>,
+    focused_client: Option<u32>,
+}
+
+impl SavedState {
+    pub fn new(screen_manager: &ScreenManager) -> Self {
+        let window_of = |frame: &xcb::x::Window| {
+            screen_manager
+                .clients()
+                .get(frame)
+                .map(|client| client.window.resource_id())
+        };
+
+        SavedState {
+            active_screen: screen_manager.active_screen_idx(),
+            screens: screen_manager
+                .screens()
+                .iter()
+                .map(|screen| SavedScreen {
+                    active_workspace: screen.active_workspace().id(),
+                    workspaces: screen
+                        .workspaces()
+                        .iter()
+                        .map(|workspace| SavedWorkspace {
+                            layout: workspace.layout().clone(),
+                            master_ratio: workspace.master_ratio(),
+                            master_count: workspace.master_count(),
+                            clients: workspace.clients().iter().filter_map(window_of).collect(),
+                            focused_client: workspace.focused_client().as_ref().and_then(window_of),
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        let path = config::state_file()?;
+        let state = serde_json::to_string(self)?;
+        std::fs::write(&path, state).context(format!("failed to write {}", path.display()))
+    }
+
+    /// whether a restart left its state behind, meaning this instance is the restarted one
+    pub fn exists() -> bool {
+        config::state_file().is_ok_and(|path| path.exists())
+    }
+
+    /// Loads the state left behind by a restart, removing it so it is only ever restored once
+    pub fn load() -> Option<Self> {
+        let path = config::state_file().ok()?;
+        let state = std::fs::read_to_string(&path).ok()?;
+        std::fs::remove_file(&path).ok();
+
+        serde_json::from_str(&state)
+            .inspect_err(|e| tracing::error!("failed to parse saved state: {e:?}"))
+            .ok()
+    }
+
+    /// every client window that was managed before restarting
+    pub fn windows(&self) -> impl Iterator<Item = xcb::x::Window> + '_ {
+        self.screens
+            .iter()
+            .flat_map(|screen| screen.workspaces.iter())
+            .flat_map(|workspace| workspace.clients.iter())
+            .map(|window| unsafe { xcb::x::Window::new(*window) })
+    }
+
+    /// Moves every adopted client back to the screen and workspace it was on. Screens and
+    /// workspaces that no longer exist are skipped, leaving their clients where adoption put them
+    pub fn restore(&self, screen_manager: &mut ScreenManager) {
+        for (screen_idx, saved_screen) in self.screens.iter().enumerate() {
+            if screen_idx.ge(&screen_manager.screens().len()) {
+                break;
+            }
+
+            for (workspace_idx, saved_workspace) in saved_screen.workspaces.iter().enumerate() {
+                if workspace_idx.ge(&screen_manager.screen(screen_idx).workspaces().len()) {
+                    break;
+                }
+
+                let frame_of = |window: &u32| {
+                    screen_manager
+                        .clients()
+                        .values()
+                        .find(|client| client.window.resource_id().eq(window))
+                        .map(|client| client.frame)
+                };
+                let frames = saved_workspace
+                    .clients
+                    .iter()
+                    .filter_map(frame_of)
+                    .collect::<Vec<_>>();
+                let focused_client = saved_workspace.focused_client.as_ref().and_then(frame_of);
+
+                for frame in frames.iter() {
+                    screen_manager.screens_mut().iter_mut().for_each(|screen| {
+                        screen
+                            .workspaces_mut()
+                            .iter_mut()
+                            .for_each(|workspace| workspace.remove_client(*frame))
+                    });
+                    if let Some(client) = screen_manager.clients_mut().get_mut(frame) {
+                        client.workspace = workspace_idx as u8;
+                    }
+                }
+
+                let workspace =
+                    &mut screen_manager.screen_mut(screen_idx).workspaces_mut()[workspace_idx];
+                frames
+                    .into_iter()
+                    .for_each(|frame| workspace.new_client(frame));
+                workspace.set_focused_client(focused_client);
+                workspace.set_layout(saved_workspace.layout.clone());
+                workspace.set_master_ratio(saved_workspace.master_ratio);
+                workspace.set_master_count(saved_workspace.master_count);
+            }
+
+            let screen = screen_manager.screen_mut(screen_idx);
+            if (saved_screen.active_workspace as usize).lt(&screen.workspaces().len()) {
+                screen.set_active_workspace(saved_screen.active_workspace);
+            }
+        }
+
+        if self.active_screen.lt(&screen_manager.screens().len()) {
+            screen_manager.set_active_screen(self.active_screen);
+        }
+        screen_manager.arrange_shared_workspaces();
+    }
+}
+
+/// The executable of the running process, which might have been replaced since it started,
+/// such as after upgrading lucky. The kernel marks a replaced executable as deleted, in which
+/// case whatever now lives at its path is used instead
+pub fn executable() -> anyhow::Result<PathBuf> {
+    let path =
+        std::fs::read_link("/proc/self/exe").context("failed to resolve the running executable")?;
+    let path = path
+        .to_str()
+        .and_then(|path| path.strip_suffix(" (deleted)"))
+        .map(PathBuf::from)
+        .unwrap_or(path);
+
+    let metadata =
+        std::fs::metadata(&path).context(format!("failed to access {}", path.display()))?;
+    if !metadata.is_file() || metadata.permissions().mode() & 0o111 == 0 {
+        anyhow::bail!("{} is not executable", path.display());
+    }
+
+    Ok(path)
+}
+
+/// Hands every client back to the root window and replaces the running process with a fresh
+/// instance of `program`, which adopts them again.
+///
+/// This only ever returns if executing the new instance failed, in which case every client
+/// was already released and has to be adopted again
+pub fn restart(
+    conn: &Arc<xcb::Connection>,
+    screen_manager: &mut ScreenManager,
+    program: &Path,
+) -> anyhow::Error {
+    // unmapping and reparenting clients must not come back to us as events, which would be
+    // handled as clients being closed or mapped again when executing the new instance fails
+    // and we adopt them once more
+    conn.send_request(&xcb::x::ChangeWindowAttributes {
+        window: screen_manager.root(),
+        value_list: &[xcb::x::Cw::EventMask(xcb::x::EventMask::NO_EVENT)],
+    });
+
+    for client in screen_manager.clients().values() {
+        conn.send_request(&xcb::x::ChangeWindowAttributes {
+            window: client.frame,
+            value_list: &[xcb::x::Cw::EventMask(xcb::x::EventMask::NO_EVENT)],
+        });
+
+        let geometry = conn
+            .wait_for_reply(conn.send_request(&xcb::x::GetGeometry {
+                drawable: xcb::x::Drawable::Window(client.frame),
+            }))
+            .map(|geometry| (geometry.x(), geometry.y()))
+            .unwrap_or_default();
+
+        conn.send_request(&xcb::x::ReparentWindow {
+            window: client.window,
+            parent: screen_manager.root(),
+            x: geometry.0,
+            y: geometry.1,
+        });
+        conn.send_request(&xcb::x::DestroyWindow {
+            window: client.frame,
+        });
+    }
+    screen_manager.clear_clients();
+
+    if let Err(e) = conn.flush() {
+        return e.into();
+    }
+
+    let mut args = std::env::args();
+    let arg0 = args.next().unwrap_or(String::from("lucky"));
+    tracing::info!("restarting {}", program.display());
+
+    std::process::Command::new(program)
+        .arg0(arg0)
+        .args(args)
+        .exec()
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::position::Position;
+    use crate::screen::Screen;
+    use config::Config;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn restored_state_matches_saved_state() {
+        let config = Rc::new(RefCell::new(Config::default()));
+        let root = unsafe { xcb::x::Window::new(0) };
+        let new_screen_manager = || {
+            let screen_positions = vec![
+                Screen::new(&config, "DP-1", Position::new(0, 0, 100, 100)),
+                Screen::new(&config, "DP-2", Position::new(100, 0, 100, 100)),
+            ];
+            ScreenManager::new(screen_positions, config.clone(), root)
+        };
+
+        let mut screen_manager = new_screen_manager();
+        for id in 1..=3 {
+            let (frame, window) =
+                unsafe { (xcb::x::Window::new(id), xcb::x::Window::new(id + 10)) };
+            screen_manager.create_client(frame, window, None, None);
+        }
+        let screen = screen_manager.screen_mut(0);
+        let moved_client = screen.active_workspace_mut().clients_mut().remove(0);
+        screen.active_workspace_mut().remove_client(moved_client);
+        screen.workspaces_mut()[2].new_client(moved_client);
+        screen.workspaces_mut()[2].set_layout(WorkspaceLayout::Monocle);
+        screen.set_active_workspace(2);
+        screen_manager.set_active_screen(1);
+        let saved_state = SavedState::new(&screen_manager);
+
+        // after restarting, every client is adopted again with a new frame
+        let mut restarted = new_screen_manager();
+        for id in 1..=3 {
+            let (frame, window) =
+                unsafe { (xcb::x::Window::new(id + 20), xcb::x::Window::new(id + 10)) };
+            restarted.create_client(frame, window, None, None);
+        }
+        saved_state.restore(&mut restarted);
+
+        assert_eq!(SavedState::new(&restarted), saved_state);
+    }
+}
@@ -0,0 +1,121 @@
+use crate::atoms::Atoms;
+use crate::xcb_utils::*;
+use config::{Config, WindowRule};
+use std::sync::Arc;
+use xcb::Xid;
+
+static WINDOW_TYPE_PREFIX: &str = "_NET_WM_WINDOW_TYPE_";
+/// window types that are managed as floating instead of being tiled
+static FLOATING_WINDOW_TYPES: [&str; 5] =
+    ["DIALOG", "UTILITY", "SPLASH", "TOOLBAR", "NOTIFICATION"];
+/// window types that are never framed, and are handled as reserved clients instead
+static RESERVED_WINDOW_TYPES: [&str; 2] = ["DOCK", "DESKTOP"];
+
+/// The properties of a window that rules can match on
+#[derive(Debug, Default)]
+pub struct WindowProperties {
+    pub class: String,
+    pub instance: String,
+    pub title: String,
+    /// `_NET_WM_WINDOW_TYPE` names without the prefix, eg: `DIALOG`
+    pub window_types: Vec<String>,
+    /// the window this one is transient for, as set by `WM_TRANSIENT_FOR`
+    pub transient_for: Option<xcb::x::Window>,
+    /// the workspace the window asked to be placed on through `_NET_WM_DESKTOP`
+    pub desktop: Option<u8>,
+}
+
+impl WindowProperties {
+    /// Reads every property from the window, missing properties are left empty
+    pub fn new(conn: &Arc<xcb::Connection>, atoms: &Atoms, window: xcb::x::Window) -> Self {
+        // WM_CLASS holds two null terminated strings, the instance followed by the class
+        let (instance, class) =
+            xcb_get_prop!(conn, window, xcb::x::ATOM_WM_CLASS, 256, xcb::x::ATOM_ANY)
+                .map(|reply| {
+                    let mut parts = reply
+                        .value::<u8>()
+                        .split(|byte| byte.eq(&0))
+                        .map(|part| String::from_utf8_lossy(part).to_string());
+                    (
+                        parts.next().unwrap_or_default(),
+                        parts.next().unwrap_or_default(),
+                    )
+                })
+                .unwrap_or_default();
+
+        let title = [atoms.net_wm_name, xcb::x::ATOM_WM_NAME]
+            .into_iter()
+            .filter_map(|atom| xcb_get_prop!(conn, window, atom, 256, xcb::x::ATOM_ANY).ok())
+            .map(|reply| String::from_utf8_lossy(reply.value::<u8>()).to_string())
+            .find(|title| !title.is_empty())
+            .unwrap_or_default();
+
+        let window_types = xcb_get_prop!(conn, window, atoms.net_wm_window_type, 32)
+            .map(|reply| reply.value::<xcb::x::Atom>().to_vec())
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|atom| {
+                conn.wait_for_reply(conn.send_request(&xcb::x::GetAtomName { atom }))
+                    .ok()
+            })
+            .filter_map(|reply| {
+                reply
+                    .name()
+                    .to_utf8()
+                    .strip_prefix(WINDOW_TYPE_PREFIX)
+                    .map(str::to_string)
+            })
+            .collect();
+
+        let transient_for = xcb_get_prop!(
+            conn,
+            window,
+            xcb::x::ATOM_WM_TRANSIENT_FOR,
+            1,
+            xcb::x::ATOM_WINDOW
+        )
+        .ok()
+        .and_then(|reply| reply.value::<xcb::x::Window>().first().copied())
+        .filter(|parent| !parent.is_none());
+
+        // windows on every desktop use 0xFFFFFFFF, which doesn't fit any workspace anyway
+        let desktop = xcb_get_prop!(conn, window, atoms.net_wm_desktop, 1, xcb::x::ATOM_CARDINAL)
+            .ok()
+            .and_then(|reply| reply.value::<u32>().first().copied())
+            .and_then(|desktop| u8::try_from(desktop).ok());
+
+        WindowProperties {
+            class,
+            instance,
+            title,
+            window_types,
+            transient_for,
+            desktop,
+        }
+    }
+
+    fn has_window_type(&self, types: &[&str]) -> bool {
+        self.window_types
+            .iter()
+            .any(|window_type| types.contains(&window_type.as_str()))
+    }
+
+    /// dialogs, splash screens and the like, as well as transient windows, shouldn't be tiled
+    pub fn should_float(&self) -> bool {
+        self.transient_for.is_some() || self.has_window_type(&FLOATING_WINDOW_TYPES)
+    }
+
+    /// docks and desktops are displayed as they are, without being managed as clients
+    pub fn is_reserved(&self) -> bool {
+        self.has_window_type(&RESERVED_WINDOW_TYPES)
+    }
+
+    /// the first rule from the configuration matching the window
+    pub fn find_rule(&self, config: &Config) -> Option<WindowRule> {
+        config
+            .rules()
+            .iter()
+            .find(|rule| rule.matches(&self.class, &self.instance, &self.title, &self.window_types))
+            .cloned()
+    }
+}
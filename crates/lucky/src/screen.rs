@@ -1,4 +1,5 @@
 use config::Config;
+use serde::{Deserialize, Serialize};
 use std::{cell::RefCell, rc::Rc};
 
 use crate::position::Position;
@@ -14,6 +15,25 @@ pub struct Client {
     pub window: xcb::x::Window,
     pub workspace: u8,
     pub visible: bool,
+    pub fullscreen: bool,
+    pub above: bool,
+    pub demands_attention: bool,
+    pub floating: bool,
+    /// geometry of the frame while the client is floating, `None` until the client is
+    /// floated for the first time, in which case it is centered on its screen
+    pub floating_position: Option<Position>,
+    /// whether the frame is displayed without borders, as requested by a rule
+    pub no_border: bool,
+}
+
+impl Client {
+    /// the border width the frame of this client should be displayed with
+    pub fn border_width(&self, config: &Config) -> u16 {
+        match self.no_border {
+            true => 0,
+            false => config.border_width(),
+        }
+    }
 }
 
 impl IntoClient for Client {
@@ -48,26 +68,61 @@ impl IntoClient for ReservedClient {
     }
 }
 
-#[derive(Default, Debug, Clone, PartialEq)]
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum WorkspaceLayout {
     #[default]
     Tall,
+    /// every client keeps its own geometry, stacked on top of each other
+    Floating,
+    /// every client fills the screen, with only the focused one visible
+    Monocle,
 }
 
+impl WorkspaceLayout {
+    /// the layout that comes after this one when cycling through layouts
+    pub fn next(&self) -> WorkspaceLayout {
+        match self {
+            WorkspaceLayout::Tall => WorkspaceLayout::Monocle,
+            WorkspaceLayout::Monocle => WorkspaceLayout::Floating,
+            WorkspaceLayout::Floating => WorkspaceLayout::Tall,
+        }
+    }
+}
+
+/// smallest fraction of the screen either column of a tall layout can be shrunk to
+const MIN_MASTER_RATIO: f32 = 0.1;
+/// biggest fraction of the screen either column of a tall layout can be grown to
+const MAX_MASTER_RATIO: f32 = 0.9;
+/// gaps can't grow past this fraction of the shortest side of the screen, so columns always
+/// keep a usable size
+const MAX_GAP_DIVISOR: u32 = 16;
+
 #[derive(Debug, PartialEq)]
 pub struct Workspace {
     id: u8,
     layout: WorkspaceLayout,
+    master_ratio: f32,
+    master_count: usize,
+    inner_gap: u32,
+    outer_gap: u32,
+    gaps_enabled: bool,
+    smart_gaps: bool,
     name: String,
     clients: Vec<xcb::x::Window>,
     focused_client: Option<xcb::x::Window>,
 }
 
 impl Workspace {
-    pub fn new(id: u8) -> Self {
+    pub fn new(id: u8, config: &Config) -> Self {
         Workspace {
             id,
             layout: Default::default(),
+            master_ratio: config.master_ratio(),
+            master_count: 1,
+            inner_gap: config.inner_gap().into(),
+            outer_gap: config.outer_gap().into(),
+            gaps_enabled: true,
+            smart_gaps: config.smart_gaps(),
             name: format!("Workspace {}", id + 1),
             clients: vec![],
             focused_client: None,
@@ -82,6 +137,72 @@ impl Workspace {
         &self.layout
     }
 
+    pub fn set_layout(&mut self, layout: WorkspaceLayout) {
+        self.layout = layout;
+    }
+
+    pub fn master_ratio(&self) -> f32 {
+        self.master_ratio
+    }
+
+    /// changes the fraction of the screen used by the master column by `amount`, clamped so
+    /// neither the master nor the side column can collapse
+    pub fn resize_master(&mut self, amount: f32) {
+        self.set_master_ratio(self.master_ratio + amount);
+    }
+
+    pub fn set_master_ratio(&mut self, master_ratio: f32) {
+        self.master_ratio = master_ratio.clamp(MIN_MASTER_RATIO, MAX_MASTER_RATIO);
+    }
+
+    pub fn master_count(&self) -> usize {
+        self.master_count
+    }
+
+    /// changes how many clients share the master column by `amount`, never going below zero.
+    /// Layouts only use as many masters as there are tiled clients
+    pub fn change_master_count(&mut self, amount: isize) {
+        self.master_count = self.master_count.saturating_add_signed(amount);
+    }
+
+    pub fn set_master_count(&mut self, master_count: usize) {
+        self.master_count = master_count;
+    }
+
+    /// whether gaps should be displayed around `tiled_clients` clients, smart gaps disable
+    /// them when there is a single one. Floating and fullscreen clients don't count, as they
+    /// are not part of the layout
+    fn has_gaps(&self, tiled_clients: usize) -> bool {
+        self.gaps_enabled && !(self.smart_gaps && tiled_clients.le(&1))
+    }
+
+    /// space between adjacent tiled clients
+    pub fn inner_gap(&self, tiled_clients: usize) -> u32 {
+        match self.has_gaps(tiled_clients) {
+            true => self.inner_gap,
+            false => 0,
+        }
+    }
+
+    /// space between tiled clients and the edges of the screen
+    pub fn outer_gap(&self, tiled_clients: usize) -> u32 {
+        match self.has_gaps(tiled_clients) {
+            true => self.outer_gap,
+            false => 0,
+        }
+    }
+
+    /// changes both the inner and outer gaps by `amount`, never going below zero nor above
+    /// `max_gap`
+    pub fn resize_gaps(&mut self, amount: i32, max_gap: u32) {
+        self.inner_gap = self.inner_gap.saturating_add_signed(amount).min(max_gap);
+        self.outer_gap = self.outer_gap.saturating_add_signed(amount).min(max_gap);
+    }
+
+    pub fn toggle_gaps(&mut self) {
+        self.gaps_enabled = !self.gaps_enabled;
+    }
+
     pub fn id(&self) -> u8 {
         self.id
     }
@@ -98,6 +219,10 @@ impl Workspace {
         &mut self.clients
     }
 
+    pub fn focused_client(&self) -> Option<xcb::x::Window> {
+        self.focused_client
+    }
+
     pub fn set_focused_client(&mut self, client: Option<xcb::x::Window>) {
         self.focused_client = client
     }
@@ -112,6 +237,8 @@ impl Workspace {
 
 #[derive(Debug)]
 pub struct Screen {
+    /// name of the monitor this screen is displayed on, as reported by randr
+    name: String,
     position: Position,
     active_workspace: u8,
     workspaces: Vec<Workspace>,
@@ -123,8 +250,9 @@ pub struct Screen {
 }
 
 impl Screen {
-    pub fn new(config: &Rc<RefCell<Config>>, position: Position) -> Self {
+    pub fn new(config: &Rc<RefCell<Config>>, name: &str, position: Position) -> Self {
         Screen {
+            name: name.to_owned(),
             position,
             active_workspace: 0,
             reserved_left_area: 0,
@@ -133,7 +261,7 @@ impl Screen {
             reserved_right_area: 0,
             reserved_clients: Vec::default(),
             workspaces: (0..config.borrow().workspaces())
-                .map(Workspace::new)
+                .map(|id| Workspace::new(id, &config.borrow()))
                 .collect(),
         }
     }
@@ -174,10 +302,46 @@ impl Screen {
         self.active_workspace = workspace;
     }
 
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
     pub fn position(&self) -> &Position {
         &self.position
     }
 
+    pub fn set_position(&mut self, position: Position) {
+        self.position = position;
+    }
+
+    /// Grows or shrinks the screen to `count` workspaces. Clients of removed workspaces are
+    /// moved to the last remaining one, and are returned so their state can be updated
+    pub fn resize_workspaces(&mut self, count: u8, config: &Config) -> Vec<xcb::x::Window> {
+        // a screen without workspaces would have nowhere to put its clients
+        let count = count.max(1);
+
+        for id in self.workspaces.len() as u8..count {
+            self.workspaces.push(Workspace::new(id, config));
+        }
+
+        let moved_clients = self
+            .workspaces
+            .drain(count as usize..)
+            .flat_map(|workspace| workspace.clients)
+            .collect::<Vec<_>>();
+
+        let last_workspace = self
+            .workspaces
+            .last_mut()
+            .expect("screen should have at least one workspace");
+        moved_clients
+            .iter()
+            .for_each(|client| last_workspace.new_client(*client));
+        self.active_workspace = self.active_workspace.min(count - 1);
+
+        moved_clients
+    }
+
     pub fn sub_left_reserved_area(&mut self, amount: u32) {
         self.reserved_left_area -= amount;
     }
@@ -218,11 +382,89 @@ impl Screen {
         self.reserved_clients.remove(reserved_client_idx);
     }
 
+    /// forgets every reserved client along with the area they reserved
+    pub fn clear_reserved_clients(&mut self) {
+        self.reserved_clients.clear();
+        self.reserved_left_area = 0;
+        self.reserved_bottom_area = 0;
+        self.reserved_top_area = 0;
+        self.reserved_right_area = 0;
+    }
+
+    /// biggest gap workspaces of this screen can be resized to
+    pub fn max_gap(&self) -> u32 {
+        self.position.width.min(self.position.height) / MAX_GAP_DIVISOR
+    }
+
+    /// area left after removing the reserved areas
     pub fn get_available_area(&self) -> Position {
-        let x = self.position.x + self.reserved_left_area as i32;
-        let y = self.position.y + self.reserved_top_area as i32;
-        let width = self.position.width - self.reserved_left_area - self.reserved_right_area;
-        let height = self.position.height - self.reserved_top_area - self.reserved_bottom_area;
-        Position::new(x, y, width, height)
+        Position::new(
+            self.position.x + self.reserved_left_area as i32,
+            self.position.y + self.reserved_top_area as i32,
+            self.position.width - self.reserved_left_area - self.reserved_right_area,
+            self.position.height - self.reserved_top_area - self.reserved_bottom_area,
+        )
+    }
+
+    /// area left for `tiled_clients` tiled clients after removing the reserved areas and the
+    /// outer gaps of the active workspace
+    pub fn get_tiled_area(&self, tiled_clients: usize) -> Position {
+        let gap = self.active_workspace().outer_gap(tiled_clients);
+        let area = self.get_available_area();
+        Position::new(
+            area.x + gap as i32,
+            area.y + gap as i32,
+            area.width.saturating_sub(gap * 2),
+            area.height.saturating_sub(gap * 2),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use xcb::XidNew;
+
+    #[test]
+    fn master_ratio_is_clamped() {
+        let mut workspace = Workspace::new(0, &Config::default());
+
+        workspace.resize_master(0.25);
+        assert!(workspace.master_ratio().eq(&0.75));
+
+        // neither column should ever collapse
+        workspace.resize_master(1.0);
+        assert!(workspace.master_ratio().eq(&MAX_MASTER_RATIO));
+        workspace.resize_master(-2.0);
+        assert!(workspace.master_ratio().eq(&MIN_MASTER_RATIO));
+    }
+
+    #[test]
+    fn smart_gaps_only_apply_with_multiple_clients() {
+        let mut workspace = Workspace::new(0, &Config::default());
+        workspace.resize_gaps(8, 100);
+        assert!(workspace.inner_gap(1).eq(&0));
+        assert!(workspace.inner_gap(2).eq(&8));
+
+        workspace.toggle_gaps();
+        assert!(workspace.inner_gap(2).eq(&0));
+    }
+
+    #[test]
+    fn removed_workspaces_move_clients_to_last_workspace() {
+        let config = Rc::new(RefCell::new(Config::default()));
+        let mut screen = Screen::new(&config, "", Position::new(0, 0, 100, 100));
+        let client = unsafe { xcb::x::Window::new(1) };
+        screen.workspaces_mut()[8].new_client(client);
+        screen.set_active_workspace(8);
+
+        let moved_clients = screen.resize_workspaces(3, &config.borrow());
+        assert!(moved_clients.eq(&[client]));
+        assert!(screen.workspaces().len().eq(&3));
+        assert!(screen.workspaces()[2].clients().eq(&[client]));
+        assert!(screen.active_workspace_id().eq(&2));
+
+        screen.resize_workspaces(5, &config.borrow());
+        assert!(screen.workspaces()[4].id().eq(&4));
     }
 }
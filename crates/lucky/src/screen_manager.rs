@@ -1,7 +1,7 @@
 use crate::ewmh::*;
 use crate::position::Position;
-use crate::screen::{Client, Screen};
-use config::Config;
+use crate::screen::{Client, Screen, Workspace, WorkspaceLayout};
+use config::{Config, WindowRule, WorkspaceMode, WorkspaceSwitch};
 use std::{cell::RefCell, collections::HashMap, ops::Add, rc::Rc};
 
 use std::sync::Arc;
@@ -21,24 +21,34 @@ pub struct ScreenManager {
     active_screen: usize,
     config: Rc<RefCell<Config>>,
     showing_desktop_status: EwmhShowingDesktop,
+    /// screens of monitors that were unplugged, keyed by monitor name, so their workspaces
+    /// can be restored when the monitor comes back
+    parked_screens: HashMap<String, Screen>,
 }
 
 impl ScreenManager {
     pub fn new(screens: Vec<Screen>, config: Rc<RefCell<Config>>, root: xcb::x::Window) -> Self {
-        ScreenManager {
+        let mut screen_manager = ScreenManager {
             active_screen: 0,
             root,
             clients: HashMap::new(),
             showing_desktop_status: EwmhShowingDesktop::Hide,
             screens,
             config,
-        }
+            parked_screens: HashMap::new(),
+        };
+        screen_manager.arrange_shared_workspaces();
+        screen_manager
     }
 
     pub fn root(&self) -> xcb::x::Window {
         self.root
     }
 
+    pub fn config(&self) -> &Rc<RefCell<Config>> {
+        &self.config
+    }
+
     pub fn clients(&self) -> &HashMap<xcb::x::Window, Client> {
         &self.clients
     }
@@ -114,6 +124,47 @@ impl ScreenManager {
         self.active_screen = active_screen_idx
     }
 
+    /// index of the screen after the active one, wrapping around to the first screen
+    pub fn next_screen_idx(&self) -> usize {
+        self.active_screen.add(1) % self.screens.len()
+    }
+
+    /// Makes `screen_idx` the active screen, returning its focused client. When the screen
+    /// had no focused client, its first client gets focused
+    pub fn focus_screen(&mut self, screen_idx: usize) -> Option<xcb::x::Window> {
+        self.active_screen = screen_idx;
+
+        let workspace = self.screens[screen_idx].active_workspace_mut();
+        if workspace.focused_client().is_none() {
+            workspace.set_focused_client(workspace.clients().first().copied());
+        }
+
+        workspace.focused_client()
+    }
+
+    /// Moves `frame` from the active screen to the active workspace of `screen_idx`, which
+    /// becomes the active screen with `frame` focused.
+    ///
+    /// Floating clients keep their place relative to the screen they are on
+    pub fn move_client_to_screen(&mut self, frame: xcb::x::Window, screen_idx: usize) {
+        let from = self.screens[self.active_screen].position().clone();
+        let to = self.screens[screen_idx].position().clone();
+        self.screens[self.active_screen]
+            .active_workspace_mut()
+            .remove_client(frame);
+
+        let workspace = self.screens[screen_idx].active_workspace_mut();
+        workspace.new_client(frame);
+        workspace.set_focused_client(Some(frame));
+
+        if let Some(client) = self.clients.get_mut(&frame) {
+            client.workspace = workspace.id();
+            translate_floating_position(client, &from, &to);
+        }
+
+        self.active_screen = screen_idx;
+    }
+
     /// Creates a new client on the active screen and active workspace on given screen
     ///
     /// When `focus_new_clients` is true on configuration, we also set the focus to the newly
@@ -121,23 +172,348 @@ impl ScreenManager {
     ///
     /// even when `focus_new_clients` is false, if the client is the only client on the workspace
     /// we focus it
-    pub fn create_client(&mut self, frame: xcb::x::Window, window: xcb::x::Window) {
+    ///
+    /// When a `rule` matches the window, it decides the screen and workspace of the client,
+    /// its initial state, and whether it gets focused at all. Otherwise the client is placed on
+    /// the `desktop` it asked for through `_NET_WM_DESKTOP`, if any
+    ///
+    /// Returns whether the client was focused on the active workspace of the active screen,
+    /// which is when it should also receive the input focus
+    pub fn create_client(
+        &mut self,
+        frame: xcb::x::Window,
+        window: xcb::x::Window,
+        rule: Option<&WindowRule>,
+        desktop: Option<u8>,
+    ) -> bool {
+        let screen_idx = rule
+            .and_then(|rule| rule.screen())
+            .filter(|screen| screen.lt(&self.screens.len()))
+            .unwrap_or(self.active_screen);
+        let workspace_id = rule
+            .and_then(|rule| rule.workspace())
+            .or(desktop)
+            .filter(|workspace| {
+                (*workspace as usize).lt(&self.screens[screen_idx].workspaces().len())
+            })
+            .unwrap_or(self.screens[screen_idx].active_workspace().id());
+        let screen_idx = self.workspace_screen_idx(screen_idx, workspace_id);
+        let screen = &mut self.screens[screen_idx];
+        let is_visible =
+            screen_idx.eq(&self.active_screen) && workspace_id.eq(&screen.active_workspace().id());
+
         self.clients.insert(
             frame,
             Client {
                 frame,
                 window,
                 visible: true,
-                workspace: self.screens[self.active_screen].active_workspace().id(),
+                fullscreen: rule.is_some_and(|rule| rule.fullscreen()),
+                above: false,
+                demands_attention: false,
+                floating: rule.is_some_and(|rule| rule.floating()),
+                floating_position: rule
+                    .and_then(|rule| rule.geometry())
+                    .map(|(x, y, width, height)| Position::new(x, y, width, height)),
+                no_border: rule.is_some_and(|rule| rule.no_border()),
+                workspace: workspace_id,
             },
         );
 
-        let screen = &mut self.screens[self.active_screen];
-        let workspace = screen.active_workspace_mut();
+        let workspace = &mut screen.workspaces_mut()[workspace_id as usize];
         workspace.new_client(frame);
 
-        if self.config.borrow().focus_new_clients() || workspace.clients().len().eq(&1) {
+        let should_focus = rule.is_none_or(|rule| rule.focus());
+        if should_focus
+            && (self.config.borrow().focus_new_clients() || workspace.clients().len().eq(&1))
+        {
             workspace.set_focused_client(Some(frame));
+            return is_visible;
+        }
+
+        false
+    }
+
+    /// Makes `workspace` the active workspace of the active screen.
+    ///
+    /// When workspaces are shared between screens and `workspace` is displayed on another
+    /// screen, the two screens either swap their workspaces, or the other screen becomes the
+    /// active screen, as configured by `workspace_switch`
+    pub fn switch_workspace(&mut self, workspace: u8) {
+        let active_screen = self.active_screen;
+        if (workspace as usize).ge(&self.screens[active_screen].workspaces().len()) {
+            return;
+        }
+
+        if self
+            .config
+            .borrow()
+            .workspace_mode()
+            .eq(&WorkspaceMode::Screen)
+        {
+            self.screens[active_screen].set_active_workspace(workspace);
+            return;
+        }
+
+        let holders = self.shared_workspace_holders();
+        let previous_workspace = self.screens[active_screen].active_workspace().id();
+        let displayed_on = self
+            .screens
+            .iter()
+            .position(|screen| screen.active_workspace().id().eq(&workspace));
+
+        match displayed_on {
+            Some(screen_idx) if screen_idx.eq(&active_screen) => {}
+            Some(screen_idx)
+                if self
+                    .config
+                    .borrow()
+                    .workspace_switch()
+                    .eq(&WorkspaceSwitch::Focus) =>
+            {
+                self.set_active_screen(screen_idx)
+            }
+            Some(screen_idx) => {
+                self.screens[screen_idx].set_active_workspace(previous_workspace);
+                self.screens[active_screen].set_active_workspace(workspace);
+            }
+            None => self.screens[active_screen].set_active_workspace(workspace),
+        }
+
+        self.move_shared_workspaces(&holders);
+    }
+
+    /// Index of the screen holding the clients of `workspace`. Every screen has its own
+    /// workspaces unless they are shared, in which case a workspace lives on the screen
+    /// displaying it, or on the first screen while it is hidden
+    pub fn workspace_screen_idx(&self, screen_idx: usize, workspace: u8) -> usize {
+        match self.config.borrow().workspace_mode() {
+            WorkspaceMode::Screen => screen_idx,
+            WorkspaceMode::Global => self
+                .screens
+                .iter()
+                .position(|screen| screen.active_workspace().id().eq(&workspace))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// When workspaces are shared between screens, makes sure no workspace is displayed on
+    /// more than one screen, and moves every workspace to the screen holding it.
+    ///
+    /// Each screen keeps a copy of every workspace, but only the copy on the screen holding
+    /// a workspace has clients
+    pub fn arrange_shared_workspaces(&mut self) {
+        let holders = self.shared_workspace_holders();
+        self.move_shared_workspaces(&holders);
+    }
+
+    /// Index of the screen holding each shared workspace, which is where they have to be moved
+    /// from once the displayed workspaces change
+    fn shared_workspace_holders(&self) -> Vec<usize> {
+        (0..self.screens[0].workspaces().len() as u8)
+            .map(|workspace| self.workspace_screen_idx(0, workspace))
+            .collect()
+    }
+
+    /// Moves every shared workspace from the screen in `holders` to the screen now holding
+    /// it, along with its layout, ratio and gaps even when it has no clients. Clients found on
+    /// any other copy are merged into it as well
+    fn move_shared_workspaces(&mut self, holders: &[usize]) {
+        if self
+            .config
+            .borrow()
+            .workspace_mode()
+            .eq(&WorkspaceMode::Screen)
+        {
+            return;
+        }
+
+        let count = self.screens[0].workspaces().len() as u8;
+        let mut displayed = vec![];
+        for screen in self.screens.iter_mut() {
+            let workspace = screen.active_workspace().id();
+            if displayed.contains(&workspace) {
+                // screens beyond the number of workspaces keep displaying a duplicate
+                if let Some(free) = (0..count).find(|id| !displayed.contains(id)) {
+                    screen.set_active_workspace(free);
+                }
+            }
+            displayed.push(screen.active_workspace().id());
+        }
+
+        for workspace in 0..count {
+            let target_idx = self.workspace_screen_idx(0, workspace);
+            let holder_idx = holders
+                .get(workspace as usize)
+                .copied()
+                .filter(|holder| holder.lt(&self.screens.len()));
+
+            // the holder goes first, so its state is the one that ends up on the target
+            let screens = holder_idx
+                .into_iter()
+                .chain((0..self.screens.len()).filter(|idx| holder_idx.ne(&Some(*idx))))
+                .collect::<Vec<_>>();
+            for screen_idx in screens {
+                let source = &self.screens[screen_idx].workspaces()[workspace as usize];
+                let is_holder = holder_idx.eq(&Some(screen_idx));
+                if screen_idx.eq(&target_idx) || (!is_holder && source.clients().is_empty()) {
+                    continue;
+                }
+
+                // moving the whole workspace keeps its layout, so it is preferred whenever the
+                // target has no clients of its own
+                let placeholder = Workspace::new(workspace, &self.config.borrow());
+                let source = std::mem::replace(
+                    &mut self.screens[screen_idx].workspaces_mut()[workspace as usize],
+                    placeholder,
+                );
+                let target = &mut self.screens[target_idx].workspaces_mut()[workspace as usize];
+                if target.clients().is_empty() {
+                    *target = source;
+                    continue;
+                }
+
+                source
+                    .clients()
+                    .iter()
+                    .for_each(|client| target.new_client(*client));
+                if target.focused_client().is_none() {
+                    target.set_focused_client(source.focused_client());
+                }
+            }
+        }
+    }
+
+    /// Updates the screens to match `monitors`, identified by their names. Screens are resized,
+    /// restored or created as needed.
+    ///
+    /// Screens of removed monitors are parked, while their clients are merged into the
+    /// workspaces with the same index on the first screen, so they remain reachable until the
+    /// monitor comes back
+    pub fn update_screens(&mut self, monitors: Vec<(String, Position)>) {
+        if monitors.is_empty() {
+            tracing::error!("no monitors available, keeping the current screens");
+            return;
+        }
+
+        let active_screen = self.screen(self.active_screen).name().to_owned();
+        let mut removed_screens = std::mem::take(&mut self.screens);
+        let mut restored_screens = vec![];
+
+        for (name, position) in monitors {
+            let mut screen = match removed_screens
+                .iter()
+                .position(|screen| screen.name().eq(&name))
+            {
+                Some(idx) => removed_screens.remove(idx),
+                None => match self.parked_screens.remove(&name) {
+                    Some(screen) => {
+                        restored_screens.push(self.screens.len());
+                        screen
+                    }
+                    None => Screen::new(&self.config, &name, position.clone()),
+                },
+            };
+            screen.set_position(position);
+            self.screens.push(screen);
+        }
+
+        for screen_idx in restored_screens {
+            self.restore_screen(screen_idx);
+        }
+
+        // reserved clients, such as status bars, are left behind as they usually handle
+        // monitor changes by themselves
+        for mut removed_screen in removed_screens {
+            let to = self.screens[0].position().clone();
+            for workspace in removed_screen.workspaces() {
+                for frame in workspace.clients() {
+                    if let Some(client) = self.clients.get_mut(frame) {
+                        translate_floating_position(client, removed_screen.position(), &to);
+                    }
+                }
+
+                let target = &mut self.screens[0].workspaces_mut()[workspace.id() as usize];
+                workspace
+                    .clients()
+                    .iter()
+                    .for_each(|client| target.new_client(*client));
+                if target.focused_client().is_none() {
+                    target.set_focused_client(workspace.focused_client());
+                }
+            }
+
+            removed_screen.clear_reserved_clients();
+            self.parked_screens
+                .insert(removed_screen.name().to_owned(), removed_screen);
+        }
+
+        self.active_screen = self
+            .screens
+            .iter()
+            .position(|screen| screen.name().eq(&active_screen))
+            .unwrap_or_default();
+        self.arrange_shared_workspaces();
+    }
+
+    /// Moves the clients of a screen that was parked back into its workspaces, taking them from
+    /// wherever they were merged into. Clients closed in the meantime are forgotten
+    fn restore_screen(&mut self, screen_idx: usize) {
+        let count = self.config.borrow().workspaces();
+        self.screens[screen_idx].resize_workspaces(count, &self.config.borrow());
+        let to = self.screens[screen_idx].position().clone();
+
+        for workspace_idx in 0..self.screens[screen_idx].workspaces().len() {
+            let frames = self.screens[screen_idx].workspaces()[workspace_idx]
+                .clients()
+                .to_vec();
+
+            for frame in frames {
+                let Some(client) = self.clients.get_mut(&frame) else {
+                    self.screens[screen_idx].workspaces_mut()[workspace_idx].remove_client(frame);
+                    continue;
+                };
+                client.workspace = workspace_idx as u8;
+
+                let from = self
+                    .screens
+                    .iter()
+                    .enumerate()
+                    .filter(|(idx, _)| idx.ne(&screen_idx))
+                    .find(|(_, screen)| {
+                        screen
+                            .workspaces()
+                            .iter()
+                            .any(|workspace| workspace.clients().contains(&frame))
+                    })
+                    .map(|(_, screen)| screen.position().clone());
+                if let Some(from) = from {
+                    translate_floating_position(client, &from, &to);
+                }
+
+                self.screens
+                    .iter_mut()
+                    .enumerate()
+                    .filter(|(idx, _)| idx.ne(&screen_idx))
+                    .flat_map(|(_, screen)| screen.workspaces_mut().iter_mut())
+                    .for_each(|workspace| workspace.remove_client(frame));
+            }
+        }
+    }
+
+    /// Changes the number of workspaces on every screen to match the configuration
+    pub fn resize_workspaces(&mut self) {
+        let count = self.config.borrow().workspaces();
+
+        for screen in self.screens.iter_mut() {
+            let moved_clients = screen.resize_workspaces(count, &self.config.borrow());
+            let last_workspace = screen.workspaces().len() as u8 - 1;
+
+            for frame in moved_clients {
+                if let Some(client) = self.clients.get_mut(&frame) {
+                    client.workspace = last_workspace;
+                }
+            }
         }
     }
 
@@ -174,6 +550,22 @@ impl ScreenManager {
         None
     }
 
+    /// Whether a client should be handled as floating, which happens when either the client
+    /// itself was set to float or the workspace it lives on uses the floating layout
+    pub fn is_floating(&self, frame: xcb::x::Window) -> bool {
+        let Some(client) = self.clients.get(&frame) else {
+            return false;
+        };
+
+        client.floating
+            || self
+                .screens
+                .iter()
+                .flat_map(|screen| screen.workspaces())
+                .find(|workspace| workspace.clients().contains(&frame))
+                .is_some_and(|workspace| workspace.layout().eq(&WorkspaceLayout::Floating))
+    }
+
     pub fn close_focused_client(&mut self) -> anyhow::Result<Option<Client>> {
         let active_screen = &mut self.screens[self.active_screen];
         if let Some(frame) = active_screen.focused_client() {
@@ -185,6 +577,23 @@ impl ScreenManager {
         Ok(None)
     }
 
+    /// Forgets every client, such as after handing all of them back to the root window
+    pub fn clear_clients(&mut self) {
+        let frames = self
+            .clients
+            .drain()
+            .map(|(frame, _)| frame)
+            .collect::<Vec<_>>();
+        self.screens
+            .iter_mut()
+            .flat_map(|screen| screen.workspaces_mut().iter_mut())
+            .for_each(|workspace| {
+                frames
+                    .iter()
+                    .for_each(|frame| workspace.remove_client(*frame))
+            });
+    }
+
     pub fn get_visible_screen_clients(&self, screen: &Screen) -> Vec<&Client> {
         screen
             .active_workspace()
@@ -198,41 +607,76 @@ impl ScreenManager {
             .collect::<Vec<&Client>>()
     }
 
-    /// when the user cursor mover from a monitor to another we need to
-    /// switch focus to that monitor,
+    /// frames of the active workspace of `screen` arranged by its layout, in order, leaving out
+    /// hidden, fullscreen and floating clients
+    pub fn tiled_clients(&self, screen: &Screen) -> Vec<xcb::x::Window> {
+        self.get_visible_screen_clients(screen)
+            .into_iter()
+            .filter(|client| {
+                client.visible && !client.fullscreen && !self.is_floating(client.frame)
+            })
+            .map(|client| client.frame)
+            .collect()
+    }
+
+    /// when the user cursor moves from a monitor to another we need to
+    /// switch focus to that monitor, `cursor_x` and `cursor_y` are relative
+    /// to the root window
     pub fn maybe_switch_screen(
         &mut self,
-        pointer: xcb::x::QueryPointerReply,
+        cursor_x: i16,
+        cursor_y: i16,
         conn: &Arc<xcb::Connection>,
         atoms: &crate::atoms::Atoms,
     ) {
-        let (cursor_x, cursor_y) = (pointer.root_x(), pointer.root_y());
-
-        for (idx, screen) in self.screens.iter().enumerate() {
-            if is_cursor_inside(cursor_x.into(), cursor_y.into(), screen.position()) {
-                self.active_screen = idx;
-                self.update_atoms(atoms, conn);
-                if let Some(client) = self.get_focused_client() {
-                    ewmh_set_active_window(conn, self.root, atoms, client.window).ok();
-                    ewmh_set_focus(conn, atoms, client.window, EwmhFocusAction::Focus).ok();
-                }
-            }
+        let Some(idx) = self.screens.iter().position(|screen| {
+            is_cursor_inside(cursor_x.into(), cursor_y.into(), screen.position())
+        }) else {
+            return;
+        };
+
+        if idx.eq(&self.active_screen) {
+            return;
+        }
+
+        self.active_screen = idx;
+        self.update_atoms(atoms, conn);
+        if let Some(client) = self.get_focused_client() {
+            ewmh_set_active_window(conn, self.root, atoms, client.window).ok();
+            ewmh_set_focus(conn, atoms, client.window, EwmhFocusAction::Focus).ok();
         }
     }
 
     /// update every EWMH necessary atoms regarding the current context
     /// of the active screen.
     ///
+    /// when workspaces are shared between screens, pagers see a single list of
+    /// desktops, each positioned on the screen holding it
+    ///
     /// although techinically some of those atoms could be updated only
     /// when changed, this is a fair tradeoff as the performance impact of
     /// this is negligible
     pub fn update_atoms(&self, atoms: &crate::atoms::Atoms, conn: &Arc<xcb::Connection>) {
         let screen = &self.screens[self.active_screen];
-        ewmh_set_desktop_viewport(conn, self.root, &self.screens, atoms).ok();
+        match self.config.borrow().workspace_mode() {
+            WorkspaceMode::Screen => {
+                let positions = self.screens.iter().map(|screen| screen.position());
+                ewmh_set_desktop_viewport(conn, self.root, positions, atoms).ok();
+                ewmh_set_wm_desktop(conn, screen, &self.clients, atoms).ok();
+            }
+            WorkspaceMode::Global => {
+                let positions = screen.workspaces().iter().map(|workspace| {
+                    self.screens[self.workspace_screen_idx(0, workspace.id())].position()
+                });
+                ewmh_set_desktop_viewport(conn, self.root, positions, atoms).ok();
+                self.screens
+                    .iter()
+                    .for_each(|screen| _ = ewmh_set_wm_desktop(conn, screen, &self.clients, atoms));
+            }
+        }
         ewmh_set_number_of_desktops(conn, self.root, screen, atoms).ok();
         ewmh_set_current_desktop(conn, self.root, screen, atoms).ok();
         ewmh_set_desktop_names(conn, self.root, screen, atoms).ok();
-        ewmh_set_wm_desktop(conn, screen, &self.clients, atoms).ok();
         ewmh_set_client_list(conn, self.root, self.clients.keys(), atoms).ok();
         ewmh_set_client_list_stacking(conn, self.root, self.clients.keys(), atoms).ok();
         ewmh_set_showing_desktop(conn, self.root, atoms, self.showing_desktop_status).ok();
@@ -249,6 +693,18 @@ fn is_cursor_inside(x: i32, y: i32, position: &Position) -> bool {
 /// calculates distance between two cartesian points.
 ///
 /// the formula is:
+/// keeps a floating client at the same place relative to the screen it is moved to
+fn translate_floating_position(client: &mut Client, from: &Position, to: &Position) {
+    client.floating_position = client.floating_position.take().map(|position| {
+        Position::new(
+            position.x - from.x + to.x,
+            position.y - from.y + to.y,
+            position.width,
+            position.height,
+        )
+    });
+}
+
 /// d=√((x2 – x1)² + (y2 – y1)²)
 /// see: https://en.wikipedia.org/wiki/Euclidean_distance
 fn euclidean_distance(x1: i32, y1: i32, x2: i32, y2: i32) -> f64 {
@@ -286,7 +742,8 @@ mod tests {
         let screens = positions
             .clone()
             .into_iter()
-            .map(|pos| Screen::new(&config, pos))
+            .enumerate()
+            .map(|(idx, pos)| Screen::new(&config, &format!("DP-{idx}"), pos))
             .collect();
         let root = unsafe { xcb::x::Window::new(0) };
         let sm = ScreenManager::new(screens, config.clone(), root);
@@ -309,11 +766,113 @@ mod tests {
 
         let root = unsafe { xcb::x::Window::new(0) };
         let sm = ScreenManager::new(
-            vec![Screen::new(&config, Position::new(1920, 0, 1920, 1080))],
+            vec![Screen::new(
+                &config,
+                "DP-1",
+                Position::new(1920, 0, 1920, 1080),
+            )],
             config,
             root,
         );
         let idx = sm.get_relative_screen_idx(Direction::Up);
         assert!(idx.is_none());
     }
+
+    #[test]
+    fn floating_follows_client_and_workspace_layout() {
+        let config = Rc::new(RefCell::new(Config::default()));
+        let root = unsafe { xcb::x::Window::new(0) };
+        let screens = vec![Screen::new(
+            &config,
+            "DP-1",
+            Position::new(0, 0, 1920, 1080),
+        )];
+        let mut sm = ScreenManager::new(screens, config, root);
+
+        let (frame_a, frame_b) = unsafe { (xcb::x::Window::new(1), xcb::x::Window::new(2)) };
+        sm.create_client(frame_a, unsafe { xcb::x::Window::new(3) }, None, None);
+        sm.create_client(frame_b, unsafe { xcb::x::Window::new(4) }, None, None);
+        assert!(!sm.is_floating(frame_a));
+        assert!(!sm.is_floating(frame_b));
+
+        sm.clients_mut().get_mut(&frame_a).unwrap().floating = true;
+        assert!(sm.is_floating(frame_a));
+        assert!(!sm.is_floating(frame_b));
+
+        sm.screen_mut(0)
+            .active_workspace_mut()
+            .set_layout(WorkspaceLayout::Floating);
+        assert!(sm.is_floating(frame_b));
+    }
+
+    #[test]
+    fn only_visible_new_clients_are_focused() {
+        let config = Rc::new(RefCell::new(Config::default()));
+        let root = unsafe { xcb::x::Window::new(0) };
+        let screens = vec![
+            Screen::new(&config, "DP-1", Position::new(0, 0, 1920, 1080)),
+            Screen::new(&config, "DP-2", Position::new(1920, 0, 1920, 1080)),
+        ];
+        let mut sm = ScreenManager::new(screens, config, root);
+
+        let window = |id| unsafe { xcb::x::Window::new(id) };
+        assert!(sm.create_client(window(1), window(2), None, None));
+        assert!(!sm.create_client(window(3), window(4), None, Some(2)));
+        assert_eq!(
+            sm.screen(0).workspaces()[2].focused_client(),
+            Some(window(3))
+        );
+
+        sm.set_active_screen(1);
+        assert!(sm.create_client(window(5), window(6), None, None));
+        assert_eq!(sm.screen(1).active_workspace().clients(), &[window(5)]);
+    }
+
+    #[test]
+    fn removed_screens_are_restored_when_their_monitor_returns() {
+        let config = Rc::new(RefCell::new(Config::default()));
+        let root = unsafe { xcb::x::Window::new(0) };
+        let screens = vec![
+            Screen::new(&config, "eDP-1", Position::new(0, 0, 1920, 1080)),
+            Screen::new(&config, "HDMI-1", Position::new(1920, 0, 1920, 1080)),
+        ];
+        let mut sm = ScreenManager::new(screens, config, root);
+
+        let frame = unsafe { xcb::x::Window::new(1) };
+        sm.set_active_screen(1);
+        sm.screen_mut(1).set_active_workspace(3);
+        sm.create_client(frame, unsafe { xcb::x::Window::new(2) }, None, None);
+        sm.clients_mut().get_mut(&frame).unwrap().floating_position =
+            Some(Position::new(2020, 100, 300, 200));
+
+        sm.update_screens(vec![(
+            String::from("eDP-1"),
+            Position::new(0, 0, 2560, 1440),
+        )]);
+        assert!(sm.screens().len().eq(&1));
+        assert!(sm.active_screen_idx().eq(&0));
+        assert!(sm.screen(0).position().eq(&Position::new(0, 0, 2560, 1440)));
+        assert!(sm.screen(0).workspaces()[3].clients().eq(&[frame]));
+        // floating clients follow their screen instead of staying on the unplugged monitor
+        assert_eq!(
+            sm.clients()[&frame].floating_position,
+            Some(Position::new(100, 100, 300, 200))
+        );
+
+        // monitors can come back in a different order
+        sm.update_screens(vec![
+            (String::from("HDMI-1"), Position::new(2560, 0, 1920, 1080)),
+            (String::from("eDP-1"), Position::new(0, 0, 2560, 1440)),
+        ]);
+        assert!(sm.screens().len().eq(&2));
+        assert!(sm.screen(0).name().eq("HDMI-1"));
+        assert!(sm.screen(0).active_workspace_id().eq(&3));
+        assert!(sm.screen(0).workspaces()[3].clients().eq(&[frame]));
+        assert!(sm.screen(1).workspaces()[3].clients().is_empty());
+        assert!(sm.screen(sm.active_screen_idx()).name().eq("eDP-1"));
+        assert_eq!(
+            sm.clients()[&frame].floating_position,
+            Some(Position::new(2660, 100, 300, 200))
+        );
+    }
 }
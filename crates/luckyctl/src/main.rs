@@ -0,0 +1,75 @@
+use anyhow::Context;
+use ipc::{Request, Response};
+use std::{
+    io::{BufRead, BufReader, Write},
+    os::unix::net::UnixStream,
+};
+
+static USAGE: &str = "usage: luckyctl <command>
+
+commands:
+    action <action>   executes an action, eg: `luckyctl action FocusLeft`
+    workspaces        lists the workspaces of every screen
+    clients           lists every managed client
+    screens           lists every screen
+    subscribe         prints every event as it happens, one JSON line per event";
+
+fn parse_request(args: &[String]) -> anyhow::Result<Request> {
+    match args {
+        [command, action] if command.eq("action") => Ok(Request::Action {
+            action: action.to_owned(),
+        }),
+        [command] if command.eq("workspaces") => Ok(Request::GetWorkspaces),
+        [command] if command.eq("clients") => Ok(Request::GetClients),
+        [command] if command.eq("screens") => Ok(Request::GetScreens),
+        [command] if command.eq("subscribe") => Ok(Request::Subscribe),
+        _ => anyhow::bail!(USAGE),
+    }
+}
+
+fn connect(request: &Request) -> anyhow::Result<BufReader<UnixStream>> {
+    let path = ipc::socket_path()?;
+    let mut stream = UnixStream::connect(&path).context(format!(
+        "failed to connect to {}, is lucky running?",
+        path.display()
+    ))?;
+
+    let request = serde_json::to_string(request).context("failed to serialize request")?;
+    writeln!(stream, "{request}").context("failed to send request")?;
+
+    Ok(BufReader::new(stream))
+}
+
+fn read_response(stream: &mut BufReader<UnixStream>) -> anyhow::Result<Response> {
+    let mut response = String::new();
+    stream
+        .read_line(&mut response)
+        .context("failed to read response")?;
+
+    serde_json::from_str(&response).context("failed to parse response")
+}
+
+/// prints every event line as is, so it can be piped straight into status bars
+fn print_events(stream: BufReader<UnixStream>) -> anyhow::Result<()> {
+    for line in stream.lines() {
+        println!("{}", line.context("failed to read event")?);
+    }
+
+    Ok(())
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let request = parse_request(&args)?;
+
+    let mut stream = connect(&request)?;
+
+    match read_response(&mut stream)? {
+        Response::Ok if matches!(request, Request::Subscribe) => print_events(stream)?,
+        Response::Ok => {}
+        Response::Error { message } => anyhow::bail!(message),
+        response => println!("{}", serde_json::to_string_pretty(&response)?),
+    }
+
+    Ok(())
+}